@@ -0,0 +1,343 @@
+use std::collections::{HashMap, HashSet};
+
+use petgraph::{algo::tarjan_scc, graph::DiGraph};
+
+use crate::{
+    direction::Direction,
+    map::{Map, Position},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitterDirection {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    /// `/` is `Mirror(Right)`, `\` is `Mirror(Left)`.
+    Mirror(Direction),
+    Splitter(SplitterDirection),
+}
+
+impl From<char> for Tile {
+    fn from(c: char) -> Self {
+        match c {
+            '.' => Self::Empty,
+            '/' => Self::Mirror(Direction::Right),
+            '\\' => Self::Mirror(Direction::Left),
+            '|' => Self::Splitter(SplitterDirection::Vertical),
+            '-' => Self::Splitter(SplitterDirection::Horizontal),
+            _ => panic!("Invalid tile"),
+        }
+    }
+}
+
+impl Tile {
+    pub fn next(&self, direction: &Direction) -> (Direction, Option<Direction>) {
+        match self {
+            Tile::Empty => (*direction, None),
+            Tile::Mirror(Direction::Right) => match direction {
+                Direction::Up => (Direction::Right, None),
+                Direction::Down => (Direction::Left, None),
+                Direction::Left => (Direction::Down, None),
+                Direction::Right => (Direction::Up, None),
+            },
+            Tile::Mirror(Direction::Left) => match direction {
+                Direction::Up => (Direction::Left, None),
+                Direction::Down => (Direction::Right, None),
+                Direction::Left => (Direction::Up, None),
+                Direction::Right => (Direction::Down, None),
+            },
+            Tile::Mirror(_) => panic!("Invalid direction"),
+            Tile::Splitter(SplitterDirection::Vertical) => match direction {
+                Direction::Left => (Direction::Down, Some(Direction::Up)),
+                Direction::Right => (Direction::Up, Some(Direction::Down)),
+                _ => (*direction, None),
+            },
+            Tile::Splitter(SplitterDirection::Horizontal) => match direction {
+                Direction::Up => (Direction::Left, Some(Direction::Right)),
+                Direction::Down => (Direction::Left, Some(Direction::Right)),
+                _ => (*direction, None),
+            },
+        }
+    }
+
+    fn directions(&self, direction: &Direction) -> impl Iterator<Item = Direction> {
+        let (first, second) = self.next(direction);
+        std::iter::once(first).chain(second)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// Returns whether the bit was not set yet.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    fn union_with(&mut self, other: &BitSet) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| index * 64 + bit)
+        })
+    }
+}
+
+/// Where a beam enters the contraption: the first tile it lights and its direction there.
+pub type Entry = (Position, Direction);
+
+pub struct Contraption {
+    map: Map<Tile>,
+    width: usize,
+    height: usize,
+}
+
+impl From<&str> for Contraption {
+    fn from(input: &str) -> Self {
+        Contraption::new(Map::from(input))
+    }
+}
+
+impl Contraption {
+    pub fn new(map: Map<Tile>) -> Self {
+        let (width, height) = (map.width(), map.height());
+        Contraption { map, width, height }
+    }
+
+    fn index(&self, position: Position) -> usize {
+        position.y * self.width + position.x
+    }
+
+    fn position(&self, index: usize) -> Position {
+        Position::from((index % self.width, index / self.width))
+    }
+
+    /// `None` once the beam leaves the map.
+    fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.to_tuple();
+        let x = position
+            .x
+            .checked_add_signed(dx)
+            .filter(|&x| x < self.width)?;
+        let y = position
+            .y
+            .checked_add_signed(dy)
+            .filter(|&y| y < self.height)?;
+        Some(Position::from((x, y)))
+    }
+
+    /// Beams entering from every side of the map.
+    pub fn edge_entries(&self) -> Vec<Entry> {
+        if self.width == 0 || self.height == 0 {
+            return vec![];
+        }
+        let (max_x, max_y) = (self.width - 1, self.height - 1);
+        let mut entries = vec![];
+        for x in 0..self.width {
+            entries.push((Position::from((x, 0)), Direction::Down));
+            entries.push((Position::from((x, max_y)), Direction::Up));
+        }
+        for y in 0..self.height {
+            entries.push((Position::from((0, y)), Direction::Right));
+            entries.push((Position::from((max_x, y)), Direction::Left));
+        }
+        entries
+    }
+
+    /// Tiles lit by a single beam, following it tile by tile with one visited bitset per
+    /// direction.
+    pub fn energize(&self, (position, direction): Entry) -> HashSet<Position> {
+        let mut visited = [(); 4].map(|_| BitSet::new(self.width * self.height));
+        let mut energized = BitSet::new(self.width * self.height);
+        let mut stack = vec![(position, direction)];
+        while let Some((position, direction)) = stack.pop() {
+            if !visited[direction as usize].insert(self.index(position)) {
+                continue;
+            }
+            energized.insert(self.index(position));
+            for next in self.map.get(position).directions(&direction) {
+                if let Some(next_position) = self.step(position, next) {
+                    stack.push((next_position, next));
+                }
+            }
+        }
+        energized.iter().map(|index| self.position(index)).collect()
+    }
+
+    // straight line through empty tiles, up to the next mirror or splitter if any
+    fn segment(&self, from: Position, direction: Direction) -> (Vec<usize>, Option<Entry>) {
+        let mut tiles = vec![];
+        let mut position = self.step(from, direction);
+        while let Some(current) = position {
+            if self.map.get(current) != Tile::Empty {
+                return (tiles, Some((current, direction)));
+            }
+            tiles.push(self.index(current));
+            position = self.step(current, direction);
+        }
+        (tiles, None)
+    }
+
+    /// Number of tiles lit from each entry. Beams are cut into segments between mirrors and
+    /// splitters; the tiles reached from each segment are computed once per strongly
+    /// connected component, then shared by every entry leading there.
+    pub fn energized_counts(&self, entries: &[Entry]) -> Vec<usize> {
+        let size = self.width * self.height;
+        let mut graph = DiGraph::<(), ()>::new();
+        let mut nodes = HashMap::new();
+        let mut own = vec![];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let position = Position::from((x, y));
+                if self.map.get(position) == Tile::Empty {
+                    continue;
+                }
+                for direction in [
+                    Direction::Up,
+                    Direction::Left,
+                    Direction::Down,
+                    Direction::Right,
+                ] {
+                    nodes.insert((position, direction), graph.add_node(()));
+                    own.push(BitSet::new(size));
+                }
+            }
+        }
+        for (&(position, direction), &node) in &nodes {
+            own[node.index()].insert(self.index(position));
+            for next in self.map.get(position).directions(&direction) {
+                let (tiles, end) = self.segment(position, next);
+                for tile in tiles {
+                    own[node.index()].insert(tile);
+                }
+                if let Some(end) = end {
+                    graph.add_edge(node, nodes[&end], ());
+                }
+            }
+        }
+
+        // components come out with their successors first
+        let components = tarjan_scc(&graph);
+        let mut component_of = vec![0; graph.node_count()];
+        for (index, component) in components.iter().enumerate() {
+            for node in component {
+                component_of[node.index()] = index;
+            }
+        }
+        let mut closures: Vec<BitSet> = Vec::with_capacity(components.len());
+        for (index, component) in components.iter().enumerate() {
+            let mut closure = BitSet::new(size);
+            for node in component {
+                closure.union_with(&own[node.index()]);
+                for successor in graph.neighbors(*node) {
+                    let successor = component_of[successor.index()];
+                    if successor != index {
+                        closure.union_with(&closures[successor]);
+                    }
+                }
+            }
+            closures.push(closure);
+        }
+
+        entries
+            .iter()
+            .map(|&(position, direction)| {
+                if let Some(node) = nodes.get(&(position, direction)) {
+                    return closures[component_of[node.index()]].len();
+                }
+                let (tiles, end) = self.segment(position, direction);
+                let mut energized = BitSet::new(size);
+                energized.insert(self.index(position));
+                for tile in tiles {
+                    energized.insert(tile);
+                }
+                if let Some(end) = end {
+                    energized.union_with(&closures[component_of[nodes[&end].index()]]);
+                }
+                energized.len()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+#[test]
+fn test_next_tile() {
+    let tile = Tile::Mirror(Direction::Right);
+    assert_eq!(tile.next(&Direction::Right), (Direction::Up, None));
+    let tile = Tile::Splitter(SplitterDirection::Vertical);
+    assert_eq!(
+        tile.next(&Direction::Left),
+        (Direction::Down, Some(Direction::Up))
+    );
+}
+
+#[test]
+fn test_energize() {
+    let contraption = Contraption::from(EXAMPLE);
+    let energized = contraption.energize((Position::from((0, 0)), Direction::Right));
+    assert_eq!(energized.len(), 46);
+    assert!(energized.contains(&Position::from((1, 9))));
+    assert!(!energized.contains(&Position::from((0, 9))));
+}
+
+#[test]
+fn test_energized_counts_match_simulation() {
+    let contraption = Contraption::from(EXAMPLE);
+    let entries = contraption.edge_entries();
+    let expected = entries
+        .iter()
+        .map(|&entry| contraption.energize(entry).len())
+        .collect::<Vec<_>>();
+    assert_eq!(contraption.energized_counts(&entries), expected);
+    assert_eq!(expected.iter().max(), Some(&51));
+}
+
+#[test]
+fn test_empty_contraption() {
+    for input in ["", "\n"] {
+        let contraption = Contraption::from(input);
+        assert!(contraption.edge_entries().is_empty());
+        assert!(contraption.energized_counts(&[]).is_empty());
+    }
+}
@@ -1,61 +1,51 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .split('\n')
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                line.chars()
-                    .filter(|char| char.is_ascii_digit())
-                    .collect::<String>()
-            })
-            .map(extract_number)
-            .sum(),
-    )
-}
+use advent_of_code::scanner::Scanner;
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
 
-fn extract_number(line: String) -> u32 {
-    let first = line.chars().next().unwrap();
-    let last = line.chars().last().unwrap();
-    let number = format!("{}{}", first, last);
-    number.parse::<u32>().unwrap()
+const SPELLED_DIGITS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub fn part_one(input: &str) -> Option<u32> {
+    calibration_sum(input, &Scanner::from_iter(DIGITS))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .split('\n')
-            .filter(|line| !line.is_empty())
-            .map(get_all_numbers_from_line)
-            .map(extract_number)
-            .sum(),
+    calibration_sum(
+        input,
+        &Scanner::from_iter(DIGITS.into_iter().chain(SPELLED_DIGITS)),
     )
 }
 
-fn get_all_numbers_from_line(line: &str) -> String {
-    let possible_numbers = vec![
-        ('1', "one"),
-        ('2', "two"),
-        ('3', "three"),
-        ('4', "four"),
-        ('5', "five"),
-        ('6', "six"),
-        ('7', "seven"),
-        ('8', "eight"),
-        ('9', "nine"),
-        ('0', "zero"),
-    ];
-    line.chars()
-        .enumerate()
-        .filter_map(|(position, k)| {
-            if k.is_ascii_digit() {
-                return Some(k);
-            }
-            possible_numbers
-                .iter()
-                .find(|number_as_string| line[position..].starts_with(number_as_string.1))
-                .map(|(number, _)| *number)
+/// First and last digits of every line, overlapping words such as "eightwo" included.
+fn calibration_sum(input: &str, digits: &Scanner<u32>) -> Option<u32> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (first, last) = digits.first_and_last(line)?;
+            Some(first.value * 10 + last.value)
         })
-        .collect::<String>()
+        .sum()
 }
 
 advent_of_code::main!(1);
@@ -64,6 +54,16 @@ advent_of_code::main!(1);
 mod tests {
     use super::*;
 
+    fn get_all_numbers_from_line(line: &str) -> String {
+        let digits = Scanner::from_iter(DIGITS.into_iter().chain(SPELLED_DIGITS));
+        let mut matches = digits.find_all(line);
+        matches.sort_by_key(|found| found.start);
+        matches
+            .iter()
+            .map(|found| found.value.to_string())
+            .collect()
+    }
+
     #[test]
     fn test_all_numbers_from_line() {
         assert_eq!(get_all_numbers_from_line("1ai5ie2"), "152");
@@ -82,4 +82,16 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part("examples", 1, 2));
         assert_eq!(result, Some(281));
     }
+
+    #[test]
+    fn test_overlapping_words() {
+        assert_eq!(part_two("eightwo\nxtwone\n"), Some(82 + 21));
+        assert_eq!(part_one("abc\n"), None);
+    }
+
+    #[test]
+    fn test_zero_digit() {
+        assert_eq!(part_one("0ab5\n"), Some(5));
+        assert_eq!(part_two("zero0two\n"), Some(2));
+    }
 }
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::{
+    integer, labelled, lines, map, pair, parse_all, separated, tag, terminated, word, Parser,
+};
+
 #[derive(Debug, Clone)]
 struct Game {
     id: u32,
@@ -7,31 +11,26 @@ struct Game {
 }
 
 const COLORS_POSSIBILITIES: [(u32, &str); 3] = [(12, "red"), (13, "green"), (14, "blue")];
+fn game<'a>() -> impl Parser<'a, Game> {
+    let cubes = pair(terminated(integer(), tag(" ")), map(word, str::to_string));
+    let throw = separated(cubes, tag(", "));
+    map(
+        labelled("Game", separated(throw, tag("; "))),
+        |(id, throws)| Game { id, throws },
+    )
+}
+
 impl TryFrom<&str> for Game {
-    type Error = &'static str;
+    type Error = String;
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if s.is_empty() {
-            return Err("Empty string");
-        }
-        let (game_str, throws) = s.split_once(':').unwrap();
-        let id = game_str.split_whitespace().last().unwrap().parse().unwrap();
-
-        let throws = throws
-            .split(';')
-            .map(|s| s.trim())
-            .map(|s| {
-                s.split(',')
-                    .map(|s| s.trim())
-                    .map(|s| s.split_once(' ').unwrap())
-                    .map(|(a, b)| (a.parse::<u32>().unwrap(), b.to_string()))
-                    .collect::<Vec<(u32, String)>>()
-            })
-            .collect::<Vec<Vec<(u32, String)>>>();
-
-        Ok(Self { id, throws })
+        Ok(parse_all(game(), s)?)
     }
 }
 
+fn parse_games(input: &str) -> Result<Vec<Game>, String> {
+    Ok(parse_all(lines(game()), input)?)
+}
+
 impl Game {
     fn get_power(&self) -> u32 {
         let mut max_throws: HashMap<String, u32> = HashMap::new();
@@ -57,9 +56,9 @@ impl Game {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .filter_map(|line| Game::try_from(line).ok())
+    parse_games(input)
+        .ok()?
+        .into_iter()
         .filter(|game| {
             game.throws.iter().all(|throw| {
                 throw.iter().all(|(a, b)| {
@@ -78,9 +77,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .filter_map(|line| Game::try_from(line).ok())
+        parse_games(input)
+            .ok()?
+            .iter()
             .map(|game| game.get_power())
             .sum(),
     )
@@ -103,4 +102,17 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part("examples", 2, 2));
         assert_eq!(result, Some(2286));
     }
+
+    #[test]
+    fn test_game_error_location() {
+        let game = Game::try_from("Game 7: 3 blue; 4 red").unwrap();
+        assert_eq!((game.id, game.throws.len()), (7, 2));
+        assert_eq!(
+            Game::try_from("Game 7: 3 blue, red").unwrap_err(),
+            "line 1, column 17: expected an integer"
+        );
+        assert!(parse_games("Game 1: 1 red\nGame two: 1 red")
+            .unwrap_err()
+            .starts_with("line 2, column 6"));
+    }
 }
@@ -1,7 +1,12 @@
 use std::ops::Mul;
 
+use advent_of_code::parse::{
+    integers, labelled, lines, map, pair, parse_all, preceded, spaces, tag,
+};
+
 pub fn part_one(input: &str) -> Option<u32> {
     build_scores_and_winning_numbers(input)
+        .ok()?
         .iter()
         .map(|(scores, winning_numbers)| {
             scores
@@ -20,6 +25,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut cards_score_counts = build_scores_and_winning_numbers(input)
+        .ok()?
         .iter()
         .map(|(scores, winning_numbers)| {
             scores
@@ -47,25 +53,13 @@ pub fn part_two(input: &str) -> Option<u32> {
         .into()
 }
 
-fn build_scores_and_winning_numbers(input: &str) -> Vec<(Vec<u32>, Vec<u32>)> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.split_once(':').unwrap().1)
-        .filter_map(|game| game.split_once('|'))
-        .map(|(scores, winning_numbers)| {
-            (
-                scores
-                    .split_whitespace()
-                    .filter_map(|s| s.parse().ok())
-                    .collect::<Vec<u32>>(),
-                winning_numbers
-                    .split_whitespace()
-                    .filter_map(|s| s.parse().ok())
-                    .collect::<Vec<u32>>(),
-            )
-        })
-        .collect()
+/// Numbers on the left of the bar, then winning numbers.
+type Card = (Vec<u32>, Vec<u32>);
+
+fn build_scores_and_winning_numbers(input: &str) -> Result<Vec<Card>, String> {
+    let numbers = pair(integers(), preceded(pair(spaces, tag("|")), integers()));
+    let card = map(labelled::<u32, _>("Card", numbers), |(_, numbers)| numbers);
+    Ok(parse_all(lines(card), input)?)
 }
 
 advent_of_code::main!(4);
@@ -85,4 +79,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", 4));
         assert_eq!(result, Some(30));
     }
+
+    #[test]
+    fn test_card_error_location() {
+        assert_eq!(
+            build_scores_and_winning_numbers("Card 1: 41 48 | 83 86\nCard 2: 13 32 || 61"),
+            Err("line 2, column 16: expected an integer".to_string())
+        );
+    }
 }
@@ -1,60 +1,62 @@
 use std::str::FromStr;
 
+use advent_of_code::{
+    interval::{Interval, RangeMap},
+    parse::{
+        blocks, delimited, integers, line_end, lines, map, pair, parse_all, preceded, tag,
+        take_while, try_map, Parser,
+    },
+};
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 struct AlmanacMap {
-    source: (u32, u32),
-    destination: (u32, u32),
-    range: u32,
+    source: Interval,
+    destination: u64,
 }
 
-fn build_maps(lines: Vec<&str>) -> Result<Vec<Vec<AlmanacMap>>, String> {
-    let mut maps: Vec<Vec<AlmanacMap>> = vec![];
-    let mut current_map: Vec<AlmanacMap> = vec![];
-    for line in lines.clone().iter().skip(1) {
-        if line.is_empty() {
-            if !current_map.is_empty() {
-                maps.push(current_map);
-                current_map = vec![];
-            }
-            continue;
-        }
-        if !line.chars().next().unwrap().is_numeric() {
-            continue;
+fn almanac_map<'a>() -> impl Parser<'a, AlmanacMap> {
+    try_map(integers::<u64>(), |numbers| {
+        let [destination, source_start, range] = numbers[..] else {
+            return Err(format!("3 numbers, got {}", numbers.len()));
+        };
+        Ok(AlmanacMap {
+            source: Interval::with_len(source_start, range),
+            destination,
+        })
+    })
+}
+
+/// The seeds line, then one block of maps per category.
+fn parse_almanac(input: &str) -> Result<(Vec<u64>, Vec<RangeMap>), String> {
+    let seeds = delimited(tag("seeds:"), integers(), pair(line_end, line_end));
+    let header = pair(take_while(|c| c != ':'), pair(tag(":"), line_end));
+    let range_map = map(preceded(header, lines(almanac_map())), |almanac_maps| {
+        let mut range_map = RangeMap::default();
+        for almanac_map in almanac_maps {
+            range_map.insert(almanac_map.source, almanac_map.destination);
         }
-        let almanac_map = AlmanacMap::from_str(line)?;
-        current_map.push(almanac_map);
-    }
-    if !current_map.is_empty() {
-        maps.push(current_map);
-    }
-    assert!(maps.len() == 7);
-    Ok(maps)
+        range_map
+    });
+    Ok(parse_all(pair(seeds, blocks(range_map)), input)?)
 }
 
 mod part_one {
     use std::str::FromStr;
 
-    use crate::{build_maps, AlmanacMap};
+    use advent_of_code::interval::RangeMap;
+
+    use crate::parse_almanac;
 
     pub struct Almanac {
-        pub seeds: Vec<u32>,
-        pub maps: Vec<Vec<AlmanacMap>>,
+        pub seeds: Vec<u64>,
+        pub maps: Vec<RangeMap>,
     }
 
     impl FromStr for Almanac {
         type Err = String;
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let mut lines = s.lines();
-            let seeds = lines
-                .nth(0)
-                .unwrap()
-                .split_whitespace()
-                .flat_map(|s| s.parse())
-                .collect::<Vec<u32>>();
-            Ok(Almanac {
-                seeds,
-                maps: build_maps(lines.collect())?,
-            })
+            let (seeds, maps) = parse_almanac(s)?;
+            Ok(Almanac { seeds, maps })
         }
     }
 }
@@ -62,31 +64,30 @@ mod part_one {
 mod part_two {
     use std::str::FromStr;
 
-    use crate::{build_maps, AlmanacMap};
+    use advent_of_code::interval::{Interval, IntervalSet, RangeMap};
+
+    use crate::parse_almanac;
 
     pub struct Almanac {
-        pub seeds: Vec<(u32, u32)>,
-        pub maps: Vec<Vec<AlmanacMap>>,
+        pub seeds: IntervalSet,
+        pub maps: Vec<RangeMap>,
     }
 
     impl FromStr for Almanac {
         type Err = String;
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let mut lines = s.lines();
-            let seeds = lines
-                .nth(0)
-                .unwrap()
-                .split_whitespace()
-                .flat_map(|s| s.parse())
-                .collect::<Vec<u32>>()
-                .as_slice()
-                .chunks(2)
-                .map(|chunk| (chunk[0], chunk[1]))
-                .collect::<Vec<(u32, u32)>>();
-            Ok(Almanac {
-                seeds,
-                maps: build_maps(lines.collect())?,
-            })
+            let (seeds, maps) = parse_almanac(s)?;
+            let pairs = seeds.chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return Err(format!(
+                    "seed ranges need an even count, got {}",
+                    seeds.len()
+                ));
+            }
+            let seeds = pairs
+                .map(|pair| Interval::with_len(pair[0], pair[1]))
+                .collect::<IntervalSet>();
+            Ok(Almanac { seeds, maps })
         }
     }
 }
@@ -94,24 +95,7 @@ mod part_two {
 impl FromStr for AlmanacMap {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s
-            .split_whitespace()
-            .flat_map(|s| s.parse::<u32>())
-            .collect::<Vec<u32>>();
-        let destination_range_start = parts.first().unwrap();
-        let source_range_start = parts.get(1).unwrap();
-        let range = parts.get(2).unwrap();
-        Ok(AlmanacMap {
-            source: (
-                *source_range_start,
-                source_range_start.wrapping_add(range.wrapping_sub(1)),
-            ),
-            destination: (
-                *destination_range_start,
-                destination_range_start.wrapping_add(range.wrapping_sub(1)),
-            ),
-            range: *range,
-        })
+        Ok(parse_all(almanac_map(), s)?)
     }
 }
 
@@ -120,18 +104,16 @@ fn test_almanac_map_from_str() {
     assert_eq!(
         AlmanacMap::from_str("50 98 2").unwrap(),
         AlmanacMap {
-            source: (98, 99),
-            destination: (50, 51),
-            range: 2,
+            source: Interval::new(98, 100),
+            destination: 50,
         }
     );
     // 52 50 48
     assert_eq!(
         AlmanacMap::from_str("52 50 48").unwrap(),
         AlmanacMap {
-            source: (50, 97),
-            destination: (52, 99),
-            range: 48,
+            source: Interval::new(50, 98),
+            destination: 52,
         }
     );
     // water-to-light map:
@@ -140,57 +122,56 @@ fn test_almanac_map_from_str() {
     assert_eq!(
         AlmanacMap::from_str("88 18 7").unwrap(),
         AlmanacMap {
-            source: (18, 24),
-            destination: (88, 94),
-            range: 7,
+            source: Interval::new(18, 25),
+            destination: 88,
         }
     );
     assert_eq!(
         AlmanacMap::from_str("18 25 70").unwrap(),
         AlmanacMap {
-            source: (25, 94),
-            destination: (18, 87),
-            range: 70,
+            source: Interval::new(25, 95),
+            destination: 18,
+        }
+    );
+    // ranges reaching the end of the u32 space must not wrap around
+    assert_eq!(
+        AlmanacMap::from_str("0 4294967295 1").unwrap(),
+        AlmanacMap {
+            source: Interval::new(4294967295, 4294967296),
+            destination: 0,
         }
     );
+    assert_eq!(
+        AlmanacMap::from_str("50 98"),
+        Err("line 1, column 1: expected 3 numbers, got 2".to_string())
+    );
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u64> {
     let almanac = part_one::Almanac::from_str(input).ok()?;
     almanac
         .seeds
         .iter()
         .map(get_min_of_locations(almanac.maps))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
         .min()
 }
 
-fn get_min_of_locations(maps: Vec<Vec<AlmanacMap>>) -> impl Fn(&u32) -> u32 {
-    move |&seed| maps.iter().fold(seed, |s, map| translate_seed(s, map))
+fn get_min_of_locations(maps: Vec<RangeMap>) -> impl Fn(&u64) -> Option<u64> {
+    move |&seed| maps.iter().try_fold(seed, translate_seed)
 }
 
-fn translate_seed(from: u32, map: &[AlmanacMap]) -> u32 {
-    map.iter()
-        .find(|tr| tr.source.0 <= from && tr.source.1 >= from)
-        .map(|tr| tr.destination.0 + (from - tr.source.0))
-        .unwrap_or(from)
+fn translate_seed(from: u64, map: &RangeMap) -> Option<u64> {
+    map.map_value(from)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u64> {
     let almanac = part_two::Almanac::from_str(input).ok()?;
     almanac
-        .seeds
+        .maps
         .iter()
-        .flat_map(|(start, range)| {
-            let array = *start..=start.wrapping_add(*range).wrapping_sub(1);
-            array
-                .map(|seed| {
-                    almanac
-                        .maps
-                        .iter()
-                        .fold(seed, |s, map| translate_seed(s, map))
-                })
-                .collect::<Vec<u32>>()
-        })
+        .try_fold(almanac.seeds, |seeds, map| map.map_set(&seeds))?
         .min()
 }
 
@@ -198,47 +179,55 @@ advent_of_code::main!(5);
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::interval::IntervalSet;
+
     use super::*;
 
     #[test]
     fn test_translate_seed() {
-        let map = [
-            AlmanacMap {
-                source: (98, 99),
-                destination: (50, 51),
-                range: 2,
-            },
-            AlmanacMap {
-                source: (50, 97),
-                destination: (52, 99),
-                range: 48,
-            },
-        ];
-        assert_eq!(translate_seed(33, &map), 33);
-        assert_eq!(translate_seed(50, &map), 52);
-        assert_eq!(translate_seed(53, &map), 55);
-        assert_eq!(translate_seed(96, &map), 98);
-        assert_eq!(translate_seed(97, &map), 99);
-        assert_eq!(translate_seed(98, &map), 50);
-        assert_eq!(translate_seed(99, &map), 51);
+        let mut map = RangeMap::default();
+        map.insert(Interval::with_len(98, 2), 50);
+        map.insert(Interval::with_len(50, 48), 52);
+        assert_eq!(translate_seed(33, &map), Some(33));
+        assert_eq!(translate_seed(50, &map), Some(52));
+        assert_eq!(translate_seed(53, &map), Some(55));
+        assert_eq!(translate_seed(96, &map), Some(98));
+        assert_eq!(translate_seed(97, &map), Some(99));
+        assert_eq!(translate_seed(98, &map), Some(50));
+        assert_eq!(translate_seed(99, &map), Some(51));
 
         // water-to-light map:
         // 88 18 7
         // 18 25 70
-        let map = [
-            AlmanacMap {
-                source: (18, 24),
-                destination: (88, 94),
-                range: 7,
-            },
-            AlmanacMap {
-                source: (25, 94),
-                destination: (18, 87),
-                range: 70,
-            },
-        ];
+        let mut map = RangeMap::default();
+        map.insert(Interval::with_len(18, 7), 88);
+        map.insert(Interval::with_len(25, 70), 18);
         let seed = 81;
-        assert_eq!(translate_seed(seed, &map), 74);
+        assert_eq!(translate_seed(seed, &map), Some(74));
+    }
+
+    #[test]
+    fn test_seed_ranges_through_one_map() {
+        let mut map = RangeMap::default();
+        map.insert(Interval::with_len(98, 2), 50);
+        map.insert(Interval::with_len(50, 48), 52);
+        let seeds =
+            IntervalSet::from_iter([Interval::with_len(79, 14), Interval::with_len(55, 13)]);
+        assert_eq!(
+            map.map_set(&seeds),
+            Some(IntervalSet::from_iter([
+                Interval::with_len(57, 13),
+                Interval::with_len(81, 14)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_odd_seed_count() {
+        assert_eq!(
+            part_two::Almanac::from_str("seeds: 79 14 55\n\na-to-b map:\n50 98 2\n").err(),
+            Some("seed ranges need an even count, got 3".to_string())
+        );
     }
 
     #[test]
@@ -252,13 +241,13 @@ mod tests {
             .iter()
             .map(|&seed| {
                 almanac.maps.iter().fold(vec![seed], |s, map| {
-                    let translation = translate_seed(*s.last().unwrap(), map);
-                    let mut result: Vec<u32> = s;
+                    let translation = translate_seed(*s.last().unwrap(), map).unwrap();
+                    let mut result: Vec<u64> = s;
                     result.push(translation);
                     result
                 })
             })
-            .collect::<Vec<Vec<u32>>>();
+            .collect::<Vec<Vec<u64>>>();
 
         let expected = vec![
             vec![79, 81, 81, 81, 74, 78, 78, 82],
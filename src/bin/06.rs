@@ -1,65 +1,35 @@
-pub fn part_one(input: &str) -> Option<u32> {
+use advent_of_code::quadratic::count_products_above;
+
+/// Races as `(time, record distance)`. With `kerning`, the spaces between digits are
+/// ignored and each line holds a single number.
+fn parse_races(input: &str, kerning: bool) -> Option<Vec<(u128, u128)>> {
     let mut lines = input.lines();
-    let times = lines
-        .next()?
-        .strip_prefix("Time:")?
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect::<Vec<u32>>();
-    let distances = lines
-        .next()?
-        .strip_prefix("Distance:")?
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect::<Vec<u32>>();
+    let mut parse_line = |header: &str| -> Option<Vec<u128>> {
+        let values = lines.next()?.strip_prefix(header)?;
+        if kerning {
+            Some(vec![values.replace(' ', "").trim().parse().ok()?])
+        } else {
+            values.split_whitespace().map(|s| s.parse().ok()).collect()
+        }
+    };
+    let times = parse_line("Time:")?;
+    let distances = parse_line("Distance:")?;
+    Some(times.into_iter().zip(distances).collect())
+}
 
-    let records = times.iter().zip(distances).collect::<Vec<(_, _)>>();
-    let possibilities = records
+fn count_ways_to_win(races: &[(u128, u128)]) -> u128 {
+    races
         .iter()
-        .map(|(time, distance)| {
-            let middle = time.div_ceil(2);
-            let part1 = (1..middle)
-                .rev()
-                .take_while(|&t| (t * (**time - t)) > *distance)
-                .collect::<Vec<u32>>();
-
-            let part2 = (middle..**time)
-                .take_while(|&t| (t * (**time - t)) > *distance)
-                .collect::<Vec<u32>>();
-            part1.iter().chain(part2.iter()).count() as u32
-        })
-        .collect::<Vec<u32>>();
-
-    possibilities.iter().product::<u32>().into()
+        .map(|&(time, distance)| count_products_above(time, distance))
+        .product()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut lines = input.lines();
-    let time = lines
-        .next()?
-        .strip_prefix("Time:")?
-        .replace(' ', "")
-        .parse::<u64>()
-        .unwrap();
-    let distance = lines
-        .next()?
-        .strip_prefix("Distance:")?
-        .replace(' ', "")
-        .trim()
-        .parse::<u64>()
-        .unwrap();
-
-    let middle = time.div_ceil(2);
-    let part1 = (1..middle)
-        .rev()
-        .take_while(|&t| (t * (time - t)) > distance)
-        .collect::<Vec<_>>();
-
-    let part2 = (middle..time)
-        .take_while(|&t| (t * (time - t)) > distance)
-        .collect::<Vec<_>>();
+pub fn part_one(input: &str) -> Option<u128> {
+    Some(count_ways_to_win(&parse_races(input, false)?))
+}
 
-    Some(part1.iter().chain(part2.iter()).count() as u32)
+pub fn part_two(input: &str) -> Option<u128> {
+    Some(count_ways_to_win(&parse_races(input, true)?))
 }
 
 advent_of_code::main!(6);
@@ -68,6 +38,16 @@ advent_of_code::main!(6);
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_races() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+        assert_eq!(
+            parse_races(input, false),
+            Some(vec![(7, 9), (15, 40), (30, 200)])
+        );
+        assert_eq!(parse_races(input, true), Some(vec![(71530, 940200)]));
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", 6));
@@ -1,83 +1,17 @@
-use std::{collections::HashMap, u64};
+use std::str::FromStr;
 
-use num::integer::lcm;
+use advent_of_code::walker::Network;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut lines = input.lines();
-    let instructions: Vec<_> = lines.next().unwrap().chars().collect();
-    lines.next();
-
-    let map: HashMap<&str, (&str, &str)> = lines
-        .filter_map(|line| {
-            let (source, possibilities) = line.split_once(" = ")?;
-            let possibilities = possibilities
-                .strip_prefix('(')?
-                .strip_suffix(')')?
-                .split_once(", ")?;
-
-            Some((source, possibilities))
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut current = "AAA";
-    let mut count = 0;
-    while current != "ZZZ" {
-        for instruction in &instructions {
-            match instruction {
-                'L' => current = map.get(current)?.0,
-                'R' => current = map.get(current)?.1,
-                _ => unreachable!(),
-            };
-            count += 1;
-            if current == "ZZZ" {
-                break;
-            }
-        }
-    }
-    Some(count)
+pub fn part_one(input: &str) -> Option<u64> {
+    Network::from_str(input)
+        .ok()?
+        .steps("AAA", |name| name == "ZZZ")
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-    let instructions: Vec<_> = lines.next().unwrap().chars().collect();
-    lines.next();
-    let nodes: HashMap<&str, (&str, &str)> = lines
-        .filter_map(|line| {
-            let (source, possibilities) = line.split_once(" = ")?;
-            let possibilities = possibilities
-                .strip_prefix('(')?
-                .strip_suffix(')')?
-                .split_once(", ")?;
-            Some((source, possibilities))
-        })
-        .collect::<HashMap<_, _>>();
-
-    let starting_nodes = nodes
-        .keys()
-        .filter(|s| s.ends_with('A'))
-        .collect::<Vec<&&str>>();
-
-    starting_nodes
-        .iter()
-        .map(|s| {
-            let mut count: u64 = 0;
-            let mut current_node = **s;
-            loop {
-                if current_node.ends_with('Z') {
-                    break;
-                }
-                let instruction = instructions[count as usize % instructions.len()];
-                match instruction {
-                    'L' => current_node = nodes.get(current_node).unwrap().0,
-                    'R' => current_node = nodes.get(current_node).unwrap().1,
-                    _ => unreachable!(),
-                };
-                count += 1;
-            }
-            count
-        })
-        .fold(1, lcm)
-        .into()
+    Network::from_str(input)
+        .ok()?
+        .steps_together(|name| name.ends_with('A'), |name| name.ends_with('Z'))
 }
 
 advent_of_code::main!(8);
@@ -98,6 +32,24 @@ mod tests {
         assert_eq!(result, Some(6));
     }
 
+    #[test]
+    fn test_export() {
+        let network = Network::from_str(&advent_of_code::template::read_file("examples", 8));
+        assert_eq!(
+            network.unwrap().export().to_dot(),
+            r#"digraph {
+    "AAA" [label="AAA", shape=box];
+    "BBB" [label="BBB", shape=box];
+    "ZZZ" [label="ZZZ", shape=box];
+    "AAA" -> "BBB";
+    "BBB" -> "AAA";
+    "BBB" -> "ZZZ";
+    "ZZZ" -> "ZZZ";
+}
+"#
+        );
+    }
+
     #[test]
     fn test_part_two_real() {
         let result = part_two(&advent_of_code::template::read_file("inputs", 8));
@@ -1,90 +1,53 @@
-pub fn part_one(input: &str) -> Option<i64> {
-    let suits = parse_input(input);
-    suits
-        .iter()
-        .map(|suit| project_next_value(suit))
-        .sum::<i64>()
-        .into()
+use advent_of_code::sequence::Polynomial;
+
+fn parse_input(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .filter_map(|s| s.parse::<i64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
 }
 
-fn project_next_value(suit: &[i64]) -> i64 {
-    let mut suit = suit.to_vec();
-    let mut last_values = vec![*suit.last().unwrap()];
-    loop {
-        if is_arythmetic(&suit) {
-            break;
-        }
-        suit = derive(&suit);
-        last_values.push(*suit.last().unwrap());
-    }
-    last_values.iter().sum::<i64>()
+fn fit(suit: &[i64]) -> Option<Polynomial> {
+    Polynomial::fit(suit, suit.len().saturating_sub(1))
+}
+
+fn project_next_value(suit: &[i64]) -> Option<i64> {
+    fit(suit)?.forwards(1).try_into().ok()
 }
 
 #[test]
 fn test_project_next_value() {
-    fn parse_line(line: &str) -> Vec<i64> {
-        line.split_whitespace()
-            .filter_map(|s| s.parse::<i64>().ok())
-            .collect::<Vec<_>>()
-    }
     let line = "20 29 36 41 44 45 44 41 36 29 20 9 -4 -19 -36 -55 -76 -99 -124 -151 -180";
-    let parsed_line = parse_line(line);
-    assert_eq!(project_next_value(&parsed_line), -211);
+    let parsed_line = &parse_input(line)[0];
+    assert_eq!(project_next_value(parsed_line), Some(-211));
 }
 
-fn project_previous_value(suit: &[i64]) -> i64 {
-    let mut suit = suit.to_vec();
-    let mut first_values = vec![*suit.first().unwrap()];
-    loop {
-        if is_arythmetic(&suit) {
-            break;
-        }
-        suit = derive(&suit);
-        first_values.push(*suit.first().unwrap());
-    }
-    first_values.iter().rev().fold(0, |res, &v| v - res)
+fn project_previous_value(suit: &[i64]) -> Option<i64> {
+    fit(suit)?.backwards(1).try_into().ok()
 }
 
 #[test]
 fn test_project_previous_value() {
-    fn parse_line(line: &str) -> Vec<i64> {
-        line.split_whitespace()
-            .filter_map(|s| s.parse::<i64>().ok())
-            .collect::<Vec<_>>()
-    }
-    let line = "10 13 16 21 30 45";
-    let parsed_line = parse_line(line);
-    assert_eq!(project_previous_value(&parsed_line), 5);
-}
-
-fn is_arythmetic(suit: &[i64]) -> bool {
-    let first = suit.first().unwrap();
-    suit.iter().all(|v| v.eq(first))
-}
-
-fn derive(suit: &[i64]) -> Vec<i64> {
-    suit.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>()
+    let parsed_line = &parse_input("10 13 16 21 30 45")[0];
+    assert_eq!(project_previous_value(parsed_line), Some(5));
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i64>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|s| s.parse::<i64>().ok())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+pub fn part_one(input: &str) -> Option<i64> {
+    parse_input(input)
+        .iter()
+        .map(|suit| project_next_value(suit))
+        .sum()
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let suits = parse_input(input);
-
-    suits
+    parse_input(input)
         .iter()
         .map(|suit| project_previous_value(suit))
-        .sum::<i64>()
-        .into()
+        .sum()
 }
 
 advent_of_code::main!(9);
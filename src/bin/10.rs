@@ -1,238 +1,56 @@
-use advent_of_code::map::{Map, Position};
+use advent_of_code::pipes::Sketch;
 
-const VERTICAL: char = '|';
-const HORIZONTAL: char = '-';
-const NORTH_TO_EST: char = 'L';
-const NORTH_TO_WEST: char = 'J';
-const SOUTH_TO_WEST: char = '7';
-const SOUTH_TO_EST: char = 'F';
-const STARTING_POSITION: char = 'S';
-const GROUND: char = '.';
-
-fn get_next_position(direction: char, position: Position, previous_position: Position) -> Position {
-    match direction {
-        VERTICAL if position.y + 1 == previous_position.y => Position {
-            x: position.x,
-            y: position.y - 1,
-        },
-        VERTICAL => Position {
-            x: position.x,
-            y: position.y + 1,
-        },
-        HORIZONTAL if position.x + 1 == previous_position.x => Position {
-            x: position.x - 1,
-            y: position.y,
-        },
-        HORIZONTAL => Position {
-            x: position.x + 1,
-            y: position.y,
-        },
-        NORTH_TO_EST if position.x + 1 == previous_position.x => Position {
-            x: position.x,
-            y: position.y - 1,
-        },
-        NORTH_TO_EST => Position {
-            x: position.x + 1,
-            y: position.y,
-        },
-        NORTH_TO_WEST if position.x - 1 == previous_position.x => Position {
-            x: position.x,
-            y: position.y - 1,
-        },
-        NORTH_TO_WEST => Position {
-            x: position.x - 1,
-            y: position.y,
-        },
-        SOUTH_TO_WEST if position.x - 1 == previous_position.x => Position {
-            x: position.x,
-            y: position.y + 1,
-        },
-        SOUTH_TO_WEST => Position {
-            x: position.x - 1,
-            y: position.y,
-        },
-        SOUTH_TO_EST if position.x + 1 == previous_position.x => Position {
-            x: position.x,
-            y: position.y + 1,
-        },
-        SOUTH_TO_EST => Position {
-            x: position.x + 1,
-            y: position.y,
-        },
-        _ => panic!("Invalid direction"),
-    }
-}
-
-#[test]
-fn test_get_next_position() {
-    assert_eq!(
-        get_next_position(VERTICAL, Position { x: 0, y: 1 }, Position { x: 0, y: 0 }),
-        Position { x: 0, y: 2 }
-    );
-    assert_eq!(
-        get_next_position(VERTICAL, Position { x: 0, y: 1 }, Position { x: 0, y: 2 }),
-        Position { x: 0, y: 0 }
-    );
-    assert_eq!(
-        get_next_position(HORIZONTAL, Position { x: 1, y: 0 }, Position { x: 2, y: 0 }),
-        Position { x: 0, y: 0 }
-    );
-    assert_eq!(
-        get_next_position(HORIZONTAL, Position { x: 1, y: 0 }, Position { x: 2, y: 0 }),
-        Position { x: 0, y: 0 }
-    );
-    assert_eq!(
-        get_next_position(
-            NORTH_TO_EST,
-            Position { x: 0, y: 1 },
-            Position { x: 0, y: 0 }
-        ),
-        Position { x: 1, y: 1 }
-    );
+pub fn part_one(input: &str) -> Option<usize> {
+    Sketch::try_from(input).ok()?.farthest().ok()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = Map::from(input);
-    Some((get_polygon(&map).len() as u32 - 1) / 2)
+pub fn part_two(input: &str) -> Option<usize> {
+    Sketch::try_from(input).ok()?.enclosed().ok()
 }
 
-fn get_polygon(map: &Map<char>) -> Vec<Position> {
-    let starting_position = map
-        .get_symbol_and_position(|c| c == STARTING_POSITION)
-        .first()
-        .unwrap()
-        .1;
-    let mut polygon: Vec<Position> = vec![starting_position];
-
-    let mut previous_position = starting_position;
-    let mut position = get_next_position_from_start(starting_position, map).unwrap();
-    polygon.push(position);
-
-    while position != starting_position {
-        let c = map.get(position);
-        let next_position = get_next_position(c, position, previous_position);
-        previous_position = position;
-        position = next_position;
-        polygon.push(position);
-    }
-    polygon
-}
+advent_of_code::main!(10);
 
-fn get_next_position_from_start(start: Position, map: &Map<char>) -> Option<Position> {
-    let adjacents = map.get_adjacents(start);
-    adjacents
+#[cfg(test)]
+fn count_enclosed_on_line(sketch: &Sketch, line: usize) -> usize {
+    let polygon = sketch.trace().unwrap();
+    sketch
+        .map
+        .enclosed_positions(&polygon)
         .iter()
-        .filter(|(c, _)| *c != GROUND)
-        .map(|(_, position)| *position)
-        .last()
-}
-
-fn ray_casting(polygon_line_points: &[&Position], line: &str) -> usize {
-    let mut is_inside = false;
-    let mut previous_char = None;
-    let mut count = 0;
-    for (x, char) in line.chars().enumerate() {
-        if !polygon_line_points.iter().any(|p| p.x == x) {
-            if is_inside {
-                count += 1;
-                previous_char = None;
-            }
-            continue;
-        }
-        if char == '-' {
-            continue;
-        }
-        if char == '|' {
-            is_inside = !is_inside;
-        }
-        if previous_char == Some('L') && char == '7' {
-            is_inside = !is_inside;
-        }
-        if previous_char == Some('F') && char == 'J' {
-            is_inside = !is_inside;
-        }
-        previous_char = Some(char);
-    }
-
-    count
+        .filter(|p| p.y == line)
+        .count()
 }
 
 #[test]
 fn test_is_inside_alternate() {
     let result: &str = &advent_of_code::template::read_file_part("examples", 10, 4);
-    let map = Map::from(result);
-    let polygon = get_polygon(&map);
-    let get_line_info = |line: usize| -> (Vec<&Position>, String) {
-        (
-            polygon.iter().filter(|p| p.y == line).collect::<Vec<_>>(),
-            map.map[line].iter().collect::<String>(),
-        )
-    };
-    let (polygon_points_for_line, line) = get_line_info(3);
-    assert_eq!(
-        ray_casting(&polygon_points_for_line, &line),
-        1,
-        "Error on {:?}",
-        line
-    );
+    let sketch = Sketch::try_from(result).unwrap();
+    assert_eq!(count_enclosed_on_line(&sketch, 3), 1);
 }
 
 #[test]
 fn test_is_inside() {
     let result: &str = &advent_of_code::template::read_file_part("examples", 10, 3);
-    let map = Map::from(result);
-    let polygon = get_polygon(&map);
-    let get_line_info = |line: usize| -> (Vec<&Position>, String) {
-        (
-            polygon.iter().filter(|p| p.y == line).collect::<Vec<_>>(),
-            map.map[line].iter().collect::<String>(),
-        )
-    };
-    let (polygon_points_for_line, line) = get_line_info(3);
-    assert_eq!(
-        ray_casting(&polygon_points_for_line, &line),
-        1,
-        "Error on {:?}",
-        line
-    );
-    let (polygon_points_for_line, line) = get_line_info(4);
-    assert_eq!(
-        ray_casting(&polygon_points_for_line, &line),
-        3,
-        "Error on {:?}",
-        line
-    );
-    let (polygon_points_for_line, line) = get_line_info(5);
-    assert_eq!(
-        ray_casting(&polygon_points_for_line, &line),
-        2,
-        "Error on {:?}",
-        line
-    );
-    let (polygon_points_for_line, line) = get_line_info(6);
-    assert_eq!(
-        ray_casting(&polygon_points_for_line, &line),
-        2,
-        "Error on {:?}",
-        line
-    );
+    let sketch = Sketch::try_from(result).unwrap();
+    assert_eq!(count_enclosed_on_line(&sketch, 3), 1);
+    assert_eq!(count_enclosed_on_line(&sketch, 4), 3);
+    assert_eq!(count_enclosed_on_line(&sketch, 5), 2);
+    assert_eq!(count_enclosed_on_line(&sketch, 6), 2);
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let map = Map::from(input);
-    let polygon = get_polygon(&map);
-
-    let mut count = 0;
-    for (y, line) in input.lines().enumerate() {
-        let revelent_polygon_points = polygon.iter().filter(|p| p.y == y).collect::<Vec<_>>();
-        count += ray_casting(&revelent_polygon_points, line);
+#[test]
+fn test_parity_scan_agrees_with_pick() {
+    for part in 2..=4 {
+        let result: &str = &advent_of_code::template::read_file_part("examples", 10, part);
+        let sketch = Sketch::try_from(result).unwrap();
+        let polygon = sketch.trace().unwrap();
+        assert_eq!(
+            sketch.enclosed().unwrap() as u128,
+            advent_of_code::polygon::interior_points(&polygon)
+        );
     }
-
-    Some(count as u64)
 }
 
-advent_of_code::main!(10);
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,73 +1,19 @@
-use std::collections::HashMap;
+use advent_of_code::{direction::Direction, platform::Platform};
 
-use advent_of_code::map::{transpose, Map};
-
-#[derive(Debug, PartialEq, Copy, Clone, PartialOrd, Ord, Eq, Hash)]
-enum Item {
-    Rounded,
-    Empty,
-    Square,
-}
-
-impl From<char> for Item {
-    fn from(c: char) -> Self {
-        match c {
-            '.' => Self::Empty,
-            'O' => Self::Rounded,
-            '#' => Self::Square,
-            _ => unreachable!("Cannot parse {} into Item", c),
-        }
-    }
-}
+const SPIN_CYCLES: usize = 1_000_000_000;
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let map: Map<Item> = Map::from(input);
-    let binding = transpose(&map.map);
-    let mut memo = HashMap::<Vec<Item>, Vec<Item>>::new();
-    let result = tilt(&binding);
-    count_rounded_square_distance(result)
-}
-
-fn count_rounded_square_distance(r: Vec<Vec<&Item>>) -> Option<usize> {
-    r.iter()
-        .map(|column| {
-            let num_cols = column.len();
-            column
-                .iter()
-                .enumerate()
-                .filter(|item| item.1 == &&Item::Rounded)
-                .map(|(i, _)| i)
-                .map(|i| num_cols - i)
-                .sum::<usize>()
-        })
-        .sum::<usize>()
-        .into()
-}
-
-fn tilt(binding: &[Vec<Item>]) -> Vec<Vec<&Item>> {
-    binding
-        .iter()
-        .filter_map(|col| -> Option<_> {
-            let binding = col.iter().collect::<Vec<_>>();
-            let x = binding
-                .split(|&&c| c == Item::Square)
-                .map(|f| {
-                    let mut col = f.to_vec().clone();
-                    col.sort();
-                    col
-                })
-                .reduce(|mut accu, mut f| {
-                    accu.push(&Item::Square);
-                    accu.append(&mut f);
-                    accu
-                });
-            x
-        })
-        .collect::<Vec<Vec<&Item>>>()
+    let mut platform = Platform::from(input);
+    platform.tilt(Direction::Up);
+    Some(platform.north_load())
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(
+        Platform::from(input)
+            .after_spin_cycles(SPIN_CYCLES)
+            .north_load(),
+    )
 }
 
 advent_of_code::main!(14);
@@ -1,14 +1,13 @@
-use std::{collections::HashMap, ops::Rem};
+use std::ops::Rem;
+
+use advent_of_code::buckets::{BucketMap, Operation};
+
+fn steps(input: &str) -> impl Iterator<Item = &str> {
+    input.split(',').map(|c| c.trim()).filter(|c| !c.is_empty())
+}
 
 pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        input
-            .split(',')
-            .map(|c| c.trim())
-            .filter(|c| !c.is_empty())
-            .map(hash)
-            .sum(),
-    )
+    Some(steps(input).map(hash).sum())
 }
 
 fn hash(c: &str) -> usize {
@@ -25,45 +24,26 @@ fn test_hash() {
     assert_eq!(hash("ot=7"), 231);
 }
 
+fn parse_step(step: &str) -> Option<Operation<String, usize>> {
+    match step.split_once('=') {
+        Some((label, focal_length)) => Some(Operation::Upsert(
+            label.to_string(),
+            focal_length.parse().ok()?,
+        )),
+        None => Some(Operation::Remove(step.strip_suffix('-')?.to_string())),
+    }
+}
+
+fn new_boxes() -> BucketMap<String, usize> {
+    BucketMap::new(256, |label| hash(label))
+}
+
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut hashmap = HashMap::<usize, Vec<(String, usize)>>::new();
-    let operations = input
-        .split(',')
-        .map(|c| c.trim())
-        .filter(|c| !c.is_empty())
-        .collect::<Vec<_>>();
-    for operation in operations {
-        match operation.contains('=') {
-            true => {
-                let (label, focal_length) = operation.split_once('=').unwrap();
-                let lens_box = hash(label);
-                let vec = hashmap.entry(lens_box).or_default();
-                // TODO: change value if already exists
-                if let Some(position) = vec.iter().position(|b| b.0 == label) {
-                    let b = vec.get_mut(position).unwrap();
-                    b.1 = focal_length.parse().unwrap();
-                } else {
-                    vec.push((label.to_string(), focal_length.parse().unwrap()));
-                }
-            }
-            false => {
-                let label = operation.replace('-', "");
-                let lens_box = hash(&label);
-                if let Some(lens_box) = hashmap.get_mut(&lens_box) {
-                    lens_box.retain(|(l, _)| l != &label);
-                }
-            }
-        }
+    let mut boxes = new_boxes();
+    for step in steps(input) {
+        boxes.apply(parse_step(step)?);
     }
-    let r = hashmap
-        .iter()
-        .flat_map(|(i, v)| {
-            v.iter()
-                .enumerate()
-                .map(move |(y, v)| (i + 1) * (y + 1) * v.1)
-        })
-        .sum();
-    Some(r)
+    Some(boxes.focusing_power())
 }
 
 advent_of_code::main!(15);
@@ -83,4 +63,27 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", 15));
         assert_eq!(result, Some(145));
     }
+
+    #[test]
+    fn test_trace() {
+        let input = advent_of_code::template::read_file("examples", 15);
+        let operations = steps(&input).map(|step| parse_step(step).unwrap());
+        let snapshots = new_boxes().trace(operations);
+        let lens = |label: &str, focal_length| (label.to_string(), focal_length);
+        assert_eq!(snapshots[0], vec![(0, vec![lens("rn", 1)])]);
+        assert_eq!(
+            snapshots[5],
+            vec![
+                (0, vec![lens("rn", 1), lens("cm", 2)]),
+                (3, vec![lens("pc", 4)])
+            ]
+        );
+        assert_eq!(
+            snapshots.last().unwrap(),
+            &vec![
+                (0, vec![lens("rn", 1), lens("cm", 2)]),
+                (3, vec![lens("ot", 7), lens("ab", 5), lens("pc", 6)]),
+            ]
+        );
+    }
 }
@@ -1,134 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::{beam::Contraption, direction::Direction, map::Position};
 
-use advent_of_code::direction::{self, Direction};
-use advent_of_code::map::{Map, Position};
-
-#[derive(Debug, Clone, Copy)]
-enum SplitterDirection {
-    Vertical,
-    Horizontal,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Tile {
-    Empty,
-    Mirror(Direction),
-    Splitter(SplitterDirection),
-}
-
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
-        match c {
-            '.' => Self::Empty,
-            '/' => Self::Mirror(Direction::Right),
-            '\\' => Self::Mirror(Direction::Left),
-            '|' => Self::Splitter(SplitterDirection::Vertical),
-            '-' => Self::Splitter(SplitterDirection::Horizontal),
-            _ => panic!("Invalid tile"),
-        }
-    }
+pub fn part_one(input: &str) -> Option<usize> {
+    let contraption = Contraption::from(input);
+    Some(
+        contraption
+            .energize((Position::from((0, 0)), Direction::Right))
+            .len(),
+    )
 }
 
-impl Tile {
-    fn next(&self, direction: &Direction) -> (Direction, Option<Direction>) {
-        match self {
-            Tile::Empty => (*direction, None),
-            Tile::Mirror(Direction::Right) => match direction {
-                Direction::Up => (Direction::Right, None),
-                Direction::Down => (Direction::Left, None),
-                Direction::Left => (Direction::Down, None),
-                Direction::Right => (Direction::Up, None),
-            },
-            Tile::Mirror(Direction::Left) => match direction {
-                Direction::Up => (Direction::Left, None),
-                Direction::Down => (Direction::Right, None),
-                Direction::Left => (Direction::Up, None),
-                Direction::Right => (Direction::Down, None),
-            },
-            Tile::Mirror(_) => panic!("Invalid direction"),
-            Tile::Splitter(SplitterDirection::Vertical) => match direction {
-                Direction::Left => (Direction::Down, Some(Direction::Up)),
-                Direction::Right => (Direction::Up, Some(Direction::Down)),
-                _ => (*direction, None),
-            },
-            Tile::Splitter(SplitterDirection::Horizontal) => match direction {
-                Direction::Up => (Direction::Left, Some(Direction::Right)),
-                Direction::Down => (Direction::Left, Some(Direction::Right)),
-                _ => (*direction, None),
-            },
-        }
-    }
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = advent_of_code::map::Map::<Tile>::from(input);
-
-    let positions = energize(&map, (Position::from((0, 0)), Direction::Right));
-
-    Some(get_positions_len(positions))
-}
-
-fn get_positions_len(positions: Vec<(Position, Direction)>) -> u32 {
-    positions
+pub fn part_two(input: &str) -> Option<usize> {
+    let contraption = Contraption::from(input);
+    contraption
+        .energized_counts(&contraption.edge_entries())
         .into_iter()
-        .map(|(p, _)| p)
-        .collect::<HashSet<Position>>()
-        .len() as u32
-}
-
-fn energize(map: &Map<Tile>, init: (Position, Direction)) -> Vec<(Position, Direction)> {
-    let mut visited_tiles = vec![];
-    let mut stack = vec![init];
-
-    while let Some((position, direction)) = stack.pop() {
-        visited_tiles.push((position, direction));
-
-        let tile = map.get(position);
-        let (next_direction, next_direction_alternative) = tile.next(&direction);
-        let next_position = next_direction.apply(&position);
-        let already_visited = visited_tiles
-            .iter()
-            .any(|(p, d)| p == &next_position && d == &next_direction);
-        if next_position != position && !map.is_out_of_bounds(next_position) && !already_visited {
-            stack.push((next_position, next_direction));
-        }
-
-        if let Some(next_direction_alternative) = next_direction_alternative {
-            let next_position = next_direction_alternative.apply(&position);
-            let already_visited = visited_tiles
-                .iter()
-                .any(|(p, d)| p == &next_position && d == &next_direction);
-            if next_position != position && !map.is_out_of_bounds(next_position) && !already_visited
-            {
-                stack.push((next_position, next_direction_alternative));
-            }
-        }
-    }
-
-    visited_tiles
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = Map::<Tile>::from(input);
-
-    let mut starting_positions: Vec<(Position, Direction)> = vec![];
-    let min_x = 0;
-    let max_x = map.map[0].len() - 1;
-    let min_y = 0;
-    let max_y = map.map.len() - 1;
-    for x in min_x..=max_x {
-        starting_positions.push((Position::from((x, min_y)), Direction::Down));
-        starting_positions.push((Position::from((x, max_y)), Direction::Up));
-    }
-    for y in min_y..=max_y {
-        starting_positions.push((Position::from((min_x, y)), Direction::Right));
-        starting_positions.push((Position::from((max_x, y)), Direction::Left));
-    }
-
-    starting_positions
-        .iter()
-        .map(|init| energize(&map, *init))
-        .map(get_positions_len)
         .max()
 }
 
@@ -157,13 +42,4 @@ mod tests {
             Position { x: 0, y: 0 }
         );
     }
-
-    #[test]
-    fn test_next_tile() {
-        // .x.
-        // >/
-        let direction = direction::Direction::Right;
-        let tile = Tile::Mirror(direction::Direction::Right);
-        assert_eq!(tile.next(&direction), (Direction::Up, None));
-    }
 }
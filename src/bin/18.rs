@@ -1,23 +1,13 @@
-use std::{
-    collections::HashSet,
-    ops::{Add, Div},
-    str::FromStr,
-};
+use std::str::FromStr;
 
-use advent_of_code::{direction::Direction, map::Position};
-use geo::{area::Area, polygon, Coord, CoordNum, GeodesicArea, LineString, Polygon};
+use advent_of_code::{
+    direction::Direction,
+    polygon::{self, Point2},
+};
 
-pub fn part_one(input: &str) -> Option<f64> {
+pub fn part_one(input: &str) -> Option<u64> {
     let instructions = get_instructions_part_1(input);
-    let borders = get_polynom_points(instructions);
-    let perimeter = &borders.len();
-    let polygon = Polygon::new(LineString::from(borders), vec![]);
-    let area = polygon.unsigned_area();
-
-    // 34771
-    println!("area {} ,perimeter: {}", area, perimeter);
-
-    Some(area + perimeter.div(2).add(1) as f64)
+    Some(get_lagoon_size(&instructions))
 }
 
 fn get_instructions_part_1(input: &str) -> Vec<(Direction, usize)> {
@@ -63,30 +53,25 @@ fn get_instructions_part_2(input: &str) -> Vec<(Direction, usize)> {
     instructions
 }
 
-fn get_polynom_points(instructions: Vec<(Direction, usize)>) -> Vec<Coord> {
-    instructions
-        .iter()
-        .fold(vec![], |mut borders, (direction, count)| {
-            for _ in 0..*count {
-                let default = Coord::from((0., 0.));
-                let last = borders.last().unwrap_or(&default);
-                borders.push(direction.apply_on_coord(last));
-            }
-            borders
-        })
+fn get_polynom_points(instructions: &[(Direction, usize)]) -> Vec<Point2> {
+    instructions.iter().fold(
+        vec![Point2::default()],
+        |mut vertices, (direction, count)| {
+            let last = vertices.last().unwrap();
+            vertices.push(direction.apply_on_point(last, *count as i64));
+            vertices
+        },
+    )
 }
 
-pub fn part_two(input: &str) -> Option<f64> {
-    let instructions = get_instructions_part_2(input);
-    let borders = get_polynom_points(instructions);
-    let perimeter = &borders.len();
-    let polygon = Polygon::new(LineString::from(borders), vec![]);
-    let area = polygon.unsigned_area();
-
-    // 34771
-    println!("area {} ,perimeter: {}", area, perimeter);
+fn get_lagoon_size(instructions: &[(Direction, usize)]) -> u64 {
+    let vertices = get_polynom_points(instructions);
+    polygon::lattice_points(&vertices) as u64
+}
 
-    Some(area + perimeter.div(2).add(1) as f64)
+pub fn part_two(input: &str) -> Option<u64> {
+    let instructions = get_instructions_part_2(input);
+    Some(get_lagoon_size(&instructions))
 }
 
 advent_of_code::main!(18);
@@ -98,12 +83,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", 18));
-        assert_eq!(result, Some(62.));
+        assert_eq!(result, Some(62));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", 18));
-        assert_eq!(result, Some(952408144115.));
+        assert_eq!(result, Some(952408144115));
     }
 }
@@ -1,10 +1,8 @@
-use std::collections::HashSet;
-
-use advent_of_code::{direction::Direction, map::Position};
-use pathfinding::{
-    directed::dfs::{dfs, dfs_reach},
-    matrix::Matrix,
+use advent_of_code::{
+    direction::Direction,
+    map::{regions::Connectivity, Map, Position},
 };
+use pathfinding::{directed::dfs::dfs_reach, matrix::Matrix};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -29,46 +27,19 @@ pub fn part_one(input: &str) -> Option<usize> {
     const LENGTH: usize = 6;
     #[cfg(not(debug_assertions))]
     const LENGTH: usize = 64;
-    let matrix = Matrix::from_rows(
-        input
-            .lines()
-            .map(|line| line.chars().map(Tile::from).collect::<Vec<Tile>>())
-            .collect::<Vec<_>>(),
-    )
-    .unwrap();
+    let map: Map<Tile> = Map::from(input);
 
-    let starting_position = matrix
-        .items()
-        .find(|(_, &tile)| tile.eq(&Tile::StartingPosition))
-        .map(|a| a.0)
-        .unwrap();
+    let starting_position = map
+        .get_symbol_and_position(|tile| tile == Tile::StartingPosition)
+        .first()
+        .unwrap()
+        .1;
 
-    dfs_reach(
-        (Position::from(starting_position), 0),
-        |(position, length)| {
-            if length == &LENGTH {
-                return vec![];
-            }
-            let mut next_positions = vec![];
-            for direction in [
-                Direction::Up,
-                Direction::Left,
-                Direction::Down,
-                Direction::Right,
-            ] {
-                let next_position = direction.apply(position);
-                if matrix
-                    .get(next_position.to_tuple())
-                    .map(|&tile| !tile.eq(&Tile::Rock))
-                    .unwrap_or(false)
-                {
-                    next_positions.push((next_position, length + 1));
-                }
-            }
-            next_positions
-        },
-    )
-    .filter(|(_, length)| length == &LENGTH)
+    map.flood_fill(&[starting_position], Connectivity::Four, |tile| {
+        tile != Tile::Rock
+    })
+    .values()
+    .filter(|&&distance| distance <= LENGTH && distance % 2 == LENGTH % 2)
     .count()
     .into()
 }
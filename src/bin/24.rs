@@ -1,83 +1,33 @@
 use std::str::FromStr;
 
-use advent_of_code::point3d::Point3D;
-use geo::{Contains, Coord, Line, Rect};
+use advent_of_code::motion::{count_intersections_xy, intercepting_trajectory, Trajectory, Window};
 
-const START: usize = 7;
-#[cfg(not(debug_assertions))]
-const START: usize = 7;
-
-const END: usize = 27;
-#[cfg(not(debug_assertions))]
-const END: usize = 7;
-
-#[derive(Debug)]
-struct Speed3D {
-    x: i32,
-    y: i32,
-    z: i32,
+fn parse(input: &str) -> Option<Vec<Trajectory>> {
+    input
+        .lines()
+        .map(Trajectory::from_str)
+        .collect::<Result<_, _>>()
+        .ok()
 }
 
-impl From<&str> for Speed3D {
-    fn from(value: &str) -> Self {
-        let mut speeds = value.split(", ");
-        Self {
-            x: speeds.next().unwrap().parse().unwrap(),
-            y: speeds.next().unwrap().parse().unwrap(),
-            z: speeds.next().unwrap().parse().unwrap(),
-        }
-    }
+pub fn part_one(input: &str) -> Option<usize> {
+    intersections_in(
+        input,
+        &Window {
+            min: 200_000_000_000_000,
+            max: 400_000_000_000_000,
+        },
+    )
 }
 
-#[derive(Debug)]
-struct Hailstone {
-    point: Point3D,
-    speed: Speed3D,
+/// Number of pairs of paths crossing inside `window`, on the x and y axes.
+fn intersections_in(input: &str, window: &Window) -> Option<usize> {
+    Some(count_intersections_xy(&parse(input)?, window))
 }
 
-impl Hailstone {
-    fn get_start_coord(&self) -> Coord {
-        let c = Coord::from((self.point.x as f64, self.point.y as f64));
-        let window = (
-            Coord::from((START as f64, START as f64)),
-            Coord::from((END as f64, END as f64)),
-        );
-        let rectangle = Rect::new(window.0, window.1);
-        let is_inside = rectangle.contains(&c);
-        if is_inside {
-            return c;
-        }
-        let is_over_max_x = c.x > END as f64;
-        let is_over_max_y = c.y > END as f64;
-        let time_delta_before_crossing_x = (END as f64 - c.x) / self.speed.x as f64;
-
-        c
-    }
-}
-
-#[test]
-fn test_get_start_point() {
-    let hailstone = Hailstone::from("12, 31, 28 @ -1, -2, -1");
-    assert_eq!(hailstone.get_start_coord(), Coord::from((10., 27.)));
-}
-
-impl From<&str> for Hailstone {
-    fn from(value: &str) -> Self {
-        let (point, speed) = value.split_once(" @ ").unwrap();
-        let point = Point3D::from_str(point).unwrap();
-        let speed = Speed3D::from(speed);
-        Self { point, speed }
-    }
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let hailstones: Vec<Hailstone> = input.lines().map(Hailstone::from).collect();
-
-    None
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<i64> {
+    let rock = intercepting_trajectory(&parse(input)?)?;
+    Some(rock.position.iter().sum())
 }
 
 advent_of_code::main!(24);
@@ -88,13 +38,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 24));
-        assert_eq!(result, None);
+        let result = intersections_in(
+            &advent_of_code::template::read_file("examples", 24),
+            &Window { min: 7, max: 27 },
+        );
+        assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", 24));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(47));
     }
 }
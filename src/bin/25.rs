@@ -1,27 +1,20 @@
-use std::collections::{HashMap, HashSet};
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let edges = input
-        .lines()
-        .flat_map(|line| {
-            let (source, destinations) = line.split_once(": ").unwrap();
-            destinations
-                .split(", ")
-                .map(|destination| (source, destination))
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let mut graph = HashMap::<&str, HashSet<&str>>::new();
-    for (source, destination) in edges {
-        graph.entry(source).or_default().insert(destination);
-        graph.entry(destination).or_default().insert(source);
-    }
+use advent_of_code::graph::cut::{stoer_wagner, LabelledGraph};
 
-    None
+fn parse(input: &str) -> LabelledGraph {
+    LabelledGraph::from_adjacency(input.lines().map(|line| {
+        let (source, destinations) = line.split_once(": ").unwrap();
+        (source, destinations.split_whitespace())
+    }))
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let graph = parse(input);
+    let cut = stoer_wagner(&graph)?;
+    let (left, right) = cut.sizes();
+    Some(left * right)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
 
@@ -37,6 +30,48 @@ mod tests {
         assert_eq!(result, Some(54));
     }
 
+    #[test]
+    fn test_cut_edges() {
+        use advent_of_code::graph::cut::{edmonds_karp, karger};
+
+        let graph = parse(&advent_of_code::template::read_file("examples", 25));
+        let normalize = |edges: Vec<(String, String)>| {
+            let mut edges = edges
+                .into_iter()
+                .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+                .collect::<Vec<_>>();
+            edges.sort();
+            edges
+        };
+        let expected = normalize(vec![
+            ("hfx".to_owned(), "pzl".to_owned()),
+            ("bvb".to_owned(), "cmg".to_owned()),
+            ("nvd".to_owned(), "jqt".to_owned()),
+        ]);
+
+        assert_eq!(normalize(stoer_wagner(&graph).unwrap().edges), expected);
+        assert_eq!(
+            normalize(karger(&graph, 100, 2023).unwrap().edges),
+            expected
+        );
+        let (flow, cut) = edmonds_karp(&graph, "jqt", "cmg").unwrap();
+        assert_eq!(flow, 3);
+        assert_eq!(normalize(cut.edges), expected);
+    }
+
+    #[test]
+    fn test_export_highlights_the_cut() {
+        let graph = parse(&advent_of_code::template::read_file("examples", 25));
+        let cut = stoer_wagner(&graph).unwrap();
+        let dot = graph
+            .export()
+            .with_highlighted(cut.edges.iter().map(|(a, b)| (a.as_str(), b.as_str())))
+            .to_dot();
+        assert_eq!(dot.lines().filter(|line| line.contains(" -- ")).count(), 33);
+        assert_eq!(dot.matches(" [color=red, penwidth=3];").count(), 3);
+        assert!(dot.contains("    \"hfx\" -- \"pzl\" [color=red, penwidth=3];\n"));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", 25));
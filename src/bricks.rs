@@ -0,0 +1,258 @@
+use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+
+use rangetools::Rangetools;
+
+use crate::point3d::Point3D;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub edges: (Point3D, Point3D),
+}
+
+impl FromStr for Brick {
+    type Err = ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let edges = s.split_once('~').unwrap();
+        let e1 = edges.0.parse()?;
+        let e2 = edges.1.parse()?;
+        Ok(Brick { edges: (e1, e2) })
+    }
+}
+
+impl Brick {
+    /// Number of cubes, both edges being included.
+    pub fn volume(&self) -> usize {
+        let (e1, e2) = &self.edges;
+        (e1.x.abs_diff(e2.x) + 1) * (e1.y.abs_diff(e2.y) + 1) * (e1.z.abs_diff(e2.z) + 1)
+    }
+
+    pub fn bottom(&self) -> usize {
+        self.edges.0.z.min(self.edges.1.z)
+    }
+
+    pub fn top(&self) -> usize {
+        self.edges.0.z.max(self.edges.1.z)
+    }
+
+    /// Cells of the xy-plane covered by the brick.
+    pub fn footprint(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (e1, e2) = self.edges;
+        let ys = e1.y.min(e2.y)..=e1.y.max(e2.y);
+        (e1.x.min(e2.x)..=e1.x.max(e2.x)).flat_map(move |x| ys.clone().map(move |y| (x, y)))
+    }
+
+    /// Moves the brick vertically so its bottom lies at `z`.
+    pub fn dropped_to(&self, z: usize) -> Brick {
+        let mut brick = *self;
+        let height = self.top() - self.bottom();
+        let (bottom, top) = if self.edges.0.z <= self.edges.1.z {
+            (&mut brick.edges.0, &mut brick.edges.1)
+        } else {
+            (&mut brick.edges.1, &mut brick.edges.0)
+        };
+        bottom.z = z;
+        top.z = z + height;
+        brick
+    }
+
+    pub fn have_contact_with(&self, other: &Brick) -> bool {
+        self.get_intersection(other).is_some()
+    }
+
+    /// Overlap of the two footprints, as inclusive `(x, y)` ranges.
+    pub fn get_intersection(&self, other: &Brick) -> Option<((usize, usize), (usize, usize))> {
+        let x_intersection =
+            (self.edges.0.x..=self.edges.1.x).intersection(other.edges.0.x..=other.edges.1.x);
+        let y_intersection =
+            (self.edges.0.y..=self.edges.1.y).intersection(other.edges.0.y..=other.edges.1.y);
+        let get_bound_value = |bound: &rangetools::Bound<usize>| match bound {
+            rangetools::Bound::Included(x) => *x,
+            _ => unreachable!(),
+        };
+
+        if x_intersection.is_empty() || y_intersection.is_empty() {
+            return None;
+        }
+
+        let x_start = get_bound_value(&x_intersection.start.to_bound());
+        let x_end = get_bound_value(&x_intersection.end.to_bound());
+        let y_start = get_bound_value(&y_intersection.start.to_bound());
+        let y_end = get_bound_value(&y_intersection.end.to_bound());
+
+        Some(((x_start, x_end), (y_start, y_end)))
+    }
+}
+
+/// Bricks at rest, sorted by bottom, along with who rests on whom.
+/// Supporters always come before the bricks they support.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settled {
+    pub bricks: Vec<Brick>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+}
+
+/// Lets every brick fall until it rests on the ground (z = 1) or on another brick,
+/// keeping the highest point reached over each xy cell.
+pub fn settle(bricks: impl IntoIterator<Item = Brick>) -> Settled {
+    let mut falling = bricks.into_iter().collect::<Vec<_>>();
+    falling.sort_by_key(Brick::bottom);
+
+    // top z and brick occupying it, per xy cell
+    let mut heights: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut settled = Settled {
+        bricks: Vec::with_capacity(falling.len()),
+        supports: vec![vec![]; falling.len()],
+        supported_by: vec![vec![]; falling.len()],
+    };
+    for (index, brick) in falling.into_iter().enumerate() {
+        let below = brick
+            .footprint()
+            .filter_map(|cell| heights.get(&cell).copied())
+            .collect::<Vec<_>>();
+        let rest = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+        let mut supporters = below
+            .iter()
+            .filter(|(z, _)| *z == rest)
+            .map(|(_, supporter)| *supporter)
+            .collect::<Vec<_>>();
+        supporters.sort();
+        supporters.dedup();
+        for &supporter in &supporters {
+            settled.supports[supporter].push(index);
+        }
+        settled.supported_by[index] = supporters;
+
+        let brick = brick.dropped_to(rest + 1);
+        for cell in brick.footprint() {
+            heights.insert(cell, (brick.top(), index));
+        }
+        settled.bricks.push(brick);
+    }
+    settled
+}
+
+impl Settled {
+    pub fn supports(&self, brick: usize) -> &[usize] {
+        &self.supports[brick]
+    }
+
+    pub fn supported_by(&self, brick: usize) -> &[usize] {
+        &self.supported_by[brick]
+    }
+
+    /// Whether removing the brick leaves every other brick in place.
+    pub fn is_safe_to_disintegrate(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    pub fn safe_to_disintegrate(&self) -> Vec<usize> {
+        (0..self.bricks.len())
+            .filter(|&brick| self.is_safe_to_disintegrate(brick))
+            .collect()
+    }
+
+    /// Immediate dominator of each brick in the support graph rooted at the ground:
+    /// the closest brick whose removal makes it fall, `None` meaning only the ground.
+    pub fn dominators(&self) -> Vec<Option<usize>> {
+        let mut dominators: Vec<Option<usize>> = Vec::with_capacity(self.bricks.len());
+        let mut depths: Vec<usize> = Vec::with_capacity(self.bricks.len());
+        for brick in 0..self.bricks.len() {
+            // supporters come first, so their dominators are already known
+            let depth = |node: Option<usize>| node.map_or(0, |n| depths[n]);
+            let common_ancestor = |mut a: Option<usize>, mut b: Option<usize>| {
+                while a != b {
+                    if depth(a) >= depth(b) {
+                        a = a.and_then(|a| dominators[a]);
+                    } else {
+                        b = b.and_then(|b| dominators[b]);
+                    }
+                }
+                a
+            };
+            let mut supporters = self.supported_by[brick].iter().map(|&s| Some(s));
+            let dominator = supporters
+                .next()
+                .and_then(|first| supporters.fold(first, common_ancestor));
+            depths.push(depth(dominator) + 1);
+            dominators.push(dominator);
+        }
+        dominators
+    }
+
+    /// Number of other bricks falling when each brick is removed, i.e. the size of its
+    /// subtree in the dominator tree.
+    pub fn chain_reactions(&self) -> Vec<usize> {
+        let dominators = self.dominators();
+        let mut falling = vec![0; self.bricks.len()];
+        for brick in (0..self.bricks.len()).rev() {
+            if let Some(dominator) = dominators[brick] {
+                falling[dominator] += falling[brick] + 1;
+            }
+        }
+        falling
+    }
+
+    pub fn chain_reaction(&self, brick: usize) -> usize {
+        self.chain_reactions()[brick]
+    }
+}
+
+#[cfg(test)]
+fn example() -> Settled {
+    settle(
+        [
+            "1,0,1~1,2,1",
+            "0,0,2~2,0,2",
+            "0,2,3~2,2,3",
+            "0,0,4~0,2,4",
+            "2,0,5~2,2,5",
+            "0,1,6~2,1,6",
+            "1,1,8~1,1,9",
+        ]
+        .map(|brick| brick.parse().unwrap()),
+    )
+}
+
+#[test]
+fn test_get_intersection() {
+    let a = Brick::from_str("1,0,1~1,2,1").unwrap();
+    let b = Brick::from_str("0,0,2~2,0,2").unwrap();
+    let c = Brick::from_str("0,2,2~2,2,2").unwrap();
+
+    assert_eq!(a.get_intersection(&b), Some(((1, 1), (0, 0))));
+    assert_eq!(b.get_intersection(&c), None);
+    assert_eq!(a.get_intersection(&c), Some(((1, 1), (2, 2))));
+    assert!(a.have_contact_with(&b) && a.have_contact_with(&c));
+}
+
+#[test]
+fn test_brick_volume() {
+    assert_eq!(Brick::from_str("0,0,0~1,1,1").unwrap().volume(), 8);
+    assert_eq!(Brick::from_str("1,0,1~1,2,1").unwrap().volume(), 3);
+}
+
+#[test]
+fn test_settle() {
+    let settled = example();
+    assert_eq!(settled.bricks[2], Brick::from_str("0,2,2~2,2,2").unwrap());
+    assert_eq!(settled.bricks[6], Brick::from_str("1,1,5~1,1,6").unwrap());
+    assert_eq!(settled.supported_by(0), &[] as &[usize]);
+    assert_eq!(settled.supports(0), &[1, 2]);
+    assert_eq!(settled.supported_by(3), &[1, 2]);
+}
+
+#[test]
+fn test_safe_to_disintegrate() {
+    assert_eq!(example().safe_to_disintegrate(), vec![1, 2, 3, 4, 6]);
+}
+
+#[test]
+fn test_chain_reactions() {
+    let settled = example();
+    assert_eq!(settled.dominators()[5], Some(0));
+    assert_eq!(settled.chain_reactions(), vec![6, 0, 0, 0, 0, 1, 0]);
+    assert_eq!(settled.chain_reaction(5), 1);
+}
@@ -0,0 +1,241 @@
+use std::{collections::HashMap, hash::Hash};
+
+pub type Hasher<K> = fn(&K) -> usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<K, V> {
+    /// Replaces the value in place when the key is already there, appends it otherwise.
+    Upsert(K, V),
+    Remove(K),
+}
+
+/// Entries of a bucket in insertion order. Removed entries leave a hole, so that removal
+/// never shifts the others, and holes get compacted once they outnumber live entries.
+#[derive(Debug, Clone)]
+struct Bucket<K, V> {
+    slots: Vec<Option<(K, V)>>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K, V> Default for Bucket<K, V> {
+    fn default() -> Self {
+        Bucket {
+            slots: vec![],
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Bucket<K, V> {
+    fn upsert(&mut self, key: K, value: V) {
+        match self.positions.get(&key) {
+            Some(&position) => self.slots[position] = Some((key, value)),
+            None => {
+                self.positions.insert(key.clone(), self.slots.len());
+                self.slots.push(Some((key, value)));
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let position = self.positions.remove(key)?;
+        let (_, value) = self.slots[position].take()?;
+        if self.slots.len() > 2 * self.positions.len() {
+            self.compact();
+        }
+        Some(value)
+    }
+
+    fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+        for (position, (key, _)) in self.slots.iter().flatten().enumerate() {
+            self.positions.insert(key.clone(), position);
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().flatten().map(|(key, value)| (key, value))
+    }
+}
+
+/// Non-empty buckets and their entries, in order.
+pub type Snapshot<K, V> = Vec<(usize, Vec<(K, V)>)>;
+
+/// Fixed number of buckets, each keeping its entries in insertion order.
+#[derive(Debug, Clone)]
+pub struct BucketMap<K, V> {
+    hasher: Hasher<K>,
+    buckets: Vec<Bucket<K, V>>,
+}
+
+impl<K: Hash + Eq + Clone, V> BucketMap<K, V> {
+    /// Panics if `bucket_count` is 0, since no key could be placed.
+    pub fn new(bucket_count: usize, hasher: Hasher<K>) -> Self {
+        assert!(bucket_count > 0, "a bucket map needs at least one bucket");
+        BucketMap {
+            hasher,
+            buckets: (0..bucket_count).map(|_| Bucket::default()).collect(),
+        }
+    }
+
+    pub fn bucket_of(&self, key: &K) -> usize {
+        (self.hasher)(key) % self.buckets.len()
+    }
+
+    pub fn upsert(&mut self, key: K, value: V) {
+        let bucket = self.bucket_of(&key);
+        self.buckets[bucket].upsert(key, value);
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let bucket = self.bucket_of(key);
+        self.buckets[bucket].remove(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let bucket = &self.buckets[self.bucket_of(key)];
+        let position = bucket.positions.get(key)?;
+        bucket.slots[*position].as_ref().map(|(_, value)| value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|b| b.positions.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn apply(&mut self, operation: Operation<K, V>) {
+        match operation {
+            Operation::Upsert(key, value) => self.upsert(key, value),
+            Operation::Remove(key) => {
+                self.remove(&key);
+            }
+        }
+    }
+
+    /// Entries of a bucket, in insertion order.
+    pub fn bucket(&self, index: usize) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets[index].iter()
+    }
+
+    /// Every entry with its bucket and its position inside it, both starting at 0.
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, &K, &V)> {
+        self.buckets.iter().enumerate().flat_map(|(index, bucket)| {
+            bucket
+                .iter()
+                .enumerate()
+                .map(move |(slot, (key, value))| (index, slot, key, value))
+        })
+    }
+
+    /// Sum over every entry of `(bucket + 1) · (slot + 1) · value`.
+    pub fn focusing_power(&self) -> usize
+    where
+        V: Copy + Into<usize>,
+    {
+        self.entries()
+            .map(|(bucket, slot, _, &value)| (bucket + 1) * (slot + 1) * value.into())
+            .sum()
+    }
+
+    pub fn snapshot(&self) -> Snapshot<K, V>
+    where
+        V: Clone,
+    {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| !bucket.positions.is_empty())
+            .map(|(index, bucket)| {
+                let entries = bucket
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+                (index, entries)
+            })
+            .collect()
+    }
+
+    /// Applies every operation, recording the state after each one.
+    pub fn trace(
+        &mut self,
+        operations: impl IntoIterator<Item = Operation<K, V>>,
+    ) -> Vec<Snapshot<K, V>>
+    where
+        V: Clone,
+    {
+        operations
+            .into_iter()
+            .map(|operation| {
+                self.apply(operation);
+                self.snapshot()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn first_letter(key: &&str) -> usize {
+    key.as_bytes()[0] as usize
+}
+
+#[test]
+fn test_upsert_keeps_order() {
+    let mut map = BucketMap::new(2, first_letter);
+    map.upsert("b", 1usize);
+    map.upsert("d", 2);
+    map.upsert("f", 3);
+    map.upsert("b", 4);
+    assert_eq!(map.get(&"b"), Some(&4));
+    assert_eq!(map.remove(&"d"), Some(2));
+    assert_eq!(map.remove(&"d"), None);
+    map.upsert("d", 5);
+    let entries = map.bucket(0).map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    assert_eq!(entries, vec![("b", 4), ("f", 3), ("d", 5)]);
+    assert_eq!(map.len(), 3);
+    // 1·1·4 + 1·2·3 + 1·3·5
+    assert_eq!(map.focusing_power(), 25);
+}
+
+#[test]
+#[should_panic(expected = "at least one bucket")]
+fn test_no_buckets() {
+    BucketMap::<&str, usize>::new(0, first_letter);
+}
+
+#[test]
+fn test_compaction() {
+    let mut map = BucketMap::new(1, first_letter);
+    for key in ["a", "b", "c", "d", "e"] {
+        map.upsert(key, 0usize);
+    }
+    for key in ["a", "c", "d"] {
+        map.remove(&key);
+    }
+    map.upsert("b", 7);
+    map.upsert("c", 8);
+    assert_eq!(
+        map.snapshot(),
+        vec![(0, vec![("b", 7), ("e", 0), ("c", 8)])]
+    );
+}
+
+#[test]
+fn test_trace() {
+    let mut map = BucketMap::new(256, first_letter);
+    let snapshots = map.trace([
+        Operation::Upsert("a", 1usize),
+        Operation::Upsert("b", 2),
+        Operation::Remove("a"),
+    ]);
+    assert_eq!(
+        snapshots,
+        vec![
+            vec![(97, vec![("a", 1)])],
+            vec![(97, vec![("a", 1)]), (98, vec![("b", 2)])],
+            vec![(98, vec![("b", 2)])],
+        ]
+    );
+}
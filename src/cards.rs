@@ -0,0 +1,199 @@
+use std::cmp::{Ordering, Reverse};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum HandType {
+    HighCard = 1,
+    OnePair = 2,
+    TwoPairs = 3,
+    ThreeOfAKind = 4,
+    FullHouse = 5,
+    FourOfAKind = 6,
+    FiveOfAKind = 7,
+}
+
+/// How hands of the same type are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compares the first card, then the second one and so on.
+    InOrder,
+    /// Compares the cards from the strongest to the weakest, wherever they are in the hand.
+    Strongest,
+}
+
+/// Maximum number of distinct cards in an ordering.
+const MAX_CARDS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Cards from the weakest to the strongest.
+    pub order: Vec<char>,
+    /// Card counting as whichever card makes the best hand.
+    pub wildcard: Option<char>,
+    pub tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn new(order: &str) -> Self {
+        let order = order.chars().collect::<Vec<_>>();
+        assert!(order.len() <= MAX_CARDS, "too many cards in {order:?}");
+        Rules {
+            order,
+            wildcard: None,
+            tie_break: TieBreak::InOrder,
+        }
+    }
+
+    pub fn with_wildcard(mut self, wildcard: char) -> Self {
+        self.wildcard = Some(wildcard);
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Camel Cards, jacks being plain cards.
+    pub fn standard() -> Self {
+        Rules::new("23456789TJQKA")
+    }
+
+    /// Camel Cards, jacks being jokers and the weakest card.
+    pub fn jokers() -> Self {
+        Rules::new("J23456789TQKA").with_wildcard('J')
+    }
+
+    pub fn rank(&self, card: char) -> Option<u8> {
+        self.order.iter().position(|&c| c == card).map(|r| r as u8)
+    }
+
+    /// Type of a five cards hand, given as ranks.
+    pub fn classify(&self, cards: &[u8]) -> HandType {
+        let wildcard = self.wildcard.and_then(|w| self.rank(w));
+        let mut counts = [0u8; MAX_CARDS];
+        let mut wildcards = 0;
+        for &card in cards {
+            if Some(card) == wildcard {
+                wildcards += 1;
+            } else {
+                counts[card as usize] += 1;
+            }
+        }
+        let (mut first, mut second) = (0, 0);
+        for &count in &counts {
+            if count > first {
+                (first, second) = (count, first);
+            } else if count > second {
+                second = count;
+            }
+        }
+        // wildcards always do best joining the largest group
+        match (first + wildcards, second) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPairs,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+
+    /// Parses `32T3K 765`.
+    pub fn parse_hand(&self, s: &str) -> Result<Hand, String> {
+        let (cards, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| format!("missing bid in {s}"))?;
+        let cards = cards
+            .chars()
+            .map(|card| {
+                self.rank(card)
+                    .ok_or_else(|| format!("unknown card {card}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut strength = cards.clone();
+        if self.tie_break == TieBreak::Strongest {
+            strength.sort_by_key(|&card| Reverse(card));
+        }
+        Ok(Hand {
+            hand_type: self.classify(&cards),
+            cards,
+            strength,
+            bid: bid.parse().map_err(|_| format!("invalid bid {bid}"))?,
+        })
+    }
+
+    /// Sum of each bid multiplied by the rank of its hand.
+    pub fn total_winnings(&self, input: &str) -> Result<u32, String> {
+        let mut hands = input
+            .lines()
+            .map(|line| self.parse_hand(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        hands.sort();
+        Ok(hands
+            .iter()
+            .enumerate()
+            .map(|(rank, hand)| (rank as u32 + 1) * hand.bid)
+            .sum())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    /// Ranks of the cards, in the order they were dealt.
+    pub cards: Vec<u8>,
+    pub bid: u32,
+    pub hand_type: HandType,
+    // cards as compared by the tie-break rule
+    strength: Vec<u8>,
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type
+            .cmp(&other.hand_type)
+            .then_with(|| self.strength.cmp(&other.strength))
+    }
+}
+
+#[test]
+fn test_classify() {
+    let rules = Rules::standard();
+    let classify = |hand: &str| rules.parse_hand(&format!("{hand} 0")).unwrap().hand_type;
+    assert_eq!(classify("6KQ2A"), HandType::HighCard);
+    assert_eq!(classify("32T3K"), HandType::OnePair);
+    assert_eq!(classify("KTJJT"), HandType::TwoPairs);
+    assert_eq!(classify("T55J5"), HandType::ThreeOfAKind);
+    assert_eq!(classify("T5T55"), HandType::FullHouse);
+    assert_eq!(classify("T5555"), HandType::FourOfAKind);
+    assert_eq!(classify("TTTTT"), HandType::FiveOfAKind);
+}
+
+#[test]
+fn test_classify_with_jokers() {
+    let rules = Rules::jokers();
+    let classify = |hand: &str| rules.parse_hand(&format!("{hand} 0")).unwrap().hand_type;
+    assert_eq!(classify("J2345"), HandType::OnePair);
+    assert_eq!(classify("T3T3J"), HandType::FullHouse);
+    assert_eq!(classify("KTJJT"), HandType::FourOfAKind);
+    assert_eq!(classify("JJJJJ"), HandType::FiveOfAKind);
+}
+
+#[test]
+fn test_tie_break() {
+    let hands = |rules: &Rules| {
+        let a = rules.parse_hand("2AAAK 1").unwrap();
+        let b = rules.parse_hand("KAAA2 2").unwrap();
+        a.cmp(&b)
+    };
+    assert_eq!(hands(&Rules::standard()), Ordering::Less);
+    let strongest = Rules::standard().with_tie_break(TieBreak::Strongest);
+    assert_eq!(hands(&strongest), Ordering::Equal);
+    assert!(Rules::standard().parse_hand("2X345 1").is_err());
+}
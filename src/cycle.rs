@@ -0,0 +1,156 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::Integer;
+
+/// A sequence `x0, f(x0), f(f(x0)), ...` that repeats every `length` steps once `start` steps
+/// have been taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Brent's algorithm: only keeps two states in memory, at the cost of re-running `step`.
+    pub fn find<S>(initial: &S, step: impl Fn(&S) -> S) -> Self
+    where
+        S: Clone + PartialEq,
+    {
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = initial.clone();
+        let mut hare = step(initial);
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = step(&hare);
+            length += 1;
+        }
+
+        let mut tortoise = initial.clone();
+        let mut hare = initial.clone();
+        for _ in 0..length {
+            hare = step(&hare);
+        }
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+        Self { start, length }
+    }
+
+    /// Runs `step` once per state, remembering every state seen so far.
+    /// Returns the states in order, up to the first one that repeats.
+    pub fn find_hashed<S>(initial: S, step: impl Fn(&S) -> S) -> (Self, Vec<S>)
+    where
+        S: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut state = initial;
+        loop {
+            if let Some(&start) = seen.get(&state) {
+                let length = states.len() - start;
+                return (Self { start, length }, states);
+            }
+            seen.insert(state.clone(), states.len());
+            let next = step(&state);
+            states.push(state);
+            state = next;
+        }
+    }
+
+    /// The smallest number of steps reaching the same state as `steps` steps.
+    pub fn equivalent_step(&self, steps: usize) -> usize {
+        if steps < self.start {
+            return steps;
+        }
+        self.start + (steps - self.start) % self.length
+    }
+}
+
+/// State reached after `steps` applications of `step`, extrapolated through its cycle.
+pub fn state_after<S>(initial: S, step: impl Fn(&S) -> S, steps: usize) -> S
+where
+    S: Clone + Eq + Hash,
+{
+    let (cycle, mut states) = Cycle::find_hashed(initial, step);
+    states.swap_remove(cycle.equivalent_step(steps))
+}
+
+/// Smallest `x` and modulus `m` such that `x ≡ residue (mod modulus)` for every congruence,
+/// any solution being `x + k·m`. Moduli need not be coprime. `None` when there is no
+/// solution, or when the combined modulus grows past what the arithmetic can hold.
+pub fn chinese_remainder(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for &(residue, modulus) in congruences {
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        let gcd = m.extended_gcd(&modulus);
+        if (residue - x) % gcd.gcd != 0 {
+            return None;
+        }
+        // x + m·k ≡ residue (mod modulus), with k = (residue - x) / gcd · m⁻¹
+        let step = modulus / gcd.gcd;
+        let k = ((residue - x) / gcd.gcd % step)
+            .checked_mul(gcd.x)?
+            .rem_euclid(step);
+        x = x.checked_add(m.checked_mul(k)?)?;
+        m = m.checked_mul(step)?;
+        x = x.rem_euclid(m);
+    }
+    Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?))
+}
+
+#[test]
+fn test_find_cycle() {
+    // 0, 1, 2, 3, 4, 2, 3, 4, ...
+    let step = |x: &u32| if *x == 4 { 2 } else { x + 1 };
+    let expected = Cycle {
+        start: 2,
+        length: 3,
+    };
+    assert_eq!(Cycle::find(&0, step), expected);
+
+    let (cycle, states) = Cycle::find_hashed(0, step);
+    assert_eq!(cycle, expected);
+    assert_eq!(states, vec![0, 1, 2, 3, 4]);
+
+    assert_eq!(expected.equivalent_step(1), 1);
+    assert_eq!(expected.equivalent_step(8), 2);
+    assert_eq!(state_after(0, step, 1_000_000_000), 4);
+}
+
+#[test]
+fn test_find_cycle_without_prefix() {
+    let step = |x: &u64| (x * 3) % 7;
+    assert_eq!(
+        Cycle::find(&1, step),
+        Cycle {
+            start: 0,
+            length: 6
+        }
+    );
+}
+
+#[test]
+fn test_chinese_remainder() {
+    assert_eq!(
+        chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+        Some((23, 105))
+    );
+    assert_eq!(chinese_remainder(&[(2, 4), (4, 6)]), Some((10, 12)));
+    assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+    assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    // moduli near 2^64 give a combined modulus too large for i128, let alone u64
+    assert_eq!(chinese_remainder(&[(5, u64::MAX)]), Some((5, u64::MAX)));
+    assert_eq!(
+        chinese_remainder(&[(1, u64::MAX), (2, u64::MAX - 1), (3, u64::MAX - 2)]),
+        None
+    );
+    assert_eq!(chinese_remainder(&[(1, 1 << 40), (2, (1 << 40) + 1)]), None);
+}
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use geo::Coord;
 
-use crate::map::Position;
+use crate::{map::Position, polygon::Point2};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
@@ -49,8 +49,16 @@ impl Direction {
         }
     }
 
+    pub fn apply_on_point(&self, point: &Point2, steps: i64) -> Point2 {
+        let (dx, dy) = self.to_tuple();
+        Point2 {
+            x: point.x + dx as i64 * steps,
+            y: point.y + dy as i64 * steps,
+        }
+    }
+
     // TODO: add logic to pop on the opposite of the map here
-    pub fn apply_with_repeat(&self, position: &Position, max: (usize, usize)) -> Position {
+    pub fn apply_with_repeat(&self, position: &Position, _max: (usize, usize)) -> Position {
         match self {
             Self::Up => Position::from((position.x, position.y.checked_sub(1).unwrap_or(1))),
             Self::Down => Position::from((position.x, position.y + 1)),
@@ -79,6 +87,15 @@ impl Direction {
         }
     }
 
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
     pub fn to_tuple(&self) -> (isize, isize) {
         match self {
             Self::Up => (0, -1),
@@ -0,0 +1,148 @@
+use crate::map::Position;
+
+/// Galaxies of an image, kept as their coordinates before expansion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    galaxies: Vec<Position>,
+    width: usize,
+    height: usize,
+}
+
+impl From<&str> for Image {
+    fn from(input: &str) -> Self {
+        let lines = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let galaxies = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Position::from((x, y)))
+            })
+            .collect();
+        Image {
+            galaxies,
+            width: lines.first().map_or(0, |line| line.len()),
+            height: lines.len(),
+        }
+    }
+}
+
+// whether each of the `size` lines holds a galaxy
+fn occupancy(occupied: impl Iterator<Item = usize>, size: usize) -> Vec<bool> {
+    let mut is_occupied = vec![false; size];
+    for index in occupied {
+        is_occupied[index] = true;
+    }
+    is_occupied
+}
+
+// coordinate of each index once every empty line before it counts `factor` times
+fn expansion(occupied: impl Iterator<Item = usize>, size: usize, factor: u64) -> Vec<u64> {
+    let mut expanded = Vec::with_capacity(size);
+    let mut coordinate = 0;
+    for occupied in occupancy(occupied, size) {
+        expanded.push(coordinate);
+        coordinate += if occupied { 1 } else { factor };
+    }
+    expanded
+}
+
+/// Sum of `|a - b|` over every pair, in O(n log n): once sorted, the i-th value lies
+/// `values[i] · i` minus the sum of the values before it away from all of them.
+pub fn pairwise_distance_sum(values: &mut [u64]) -> u64 {
+    values.sort_unstable();
+    let mut before = 0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            let distance = value * index as u64 - before;
+            before += value;
+            distance
+        })
+        .sum()
+}
+
+impl Image {
+    /// Empty rows and columns, in that order.
+    pub fn empty_lines(&self) -> (Vec<usize>, Vec<usize>) {
+        let empty = |occupied: Vec<bool>| {
+            (0..occupied.len())
+                .filter(|&index| !occupied[index])
+                .collect::<Vec<_>>()
+        };
+        (
+            empty(occupancy(self.galaxies.iter().map(|g| g.y), self.height)),
+            empty(occupancy(self.galaxies.iter().map(|g| g.x), self.width)),
+        )
+    }
+
+    /// Galaxies once every empty row and column has been replaced by `factor` of them.
+    pub fn expanded(&self, factor: u64) -> Vec<(u64, u64)> {
+        let xs = expansion(self.galaxies.iter().map(|g| g.x), self.width, factor);
+        let ys = expansion(self.galaxies.iter().map(|g| g.y), self.height, factor);
+        self.galaxies
+            .iter()
+            .map(|galaxy| (xs[galaxy.x], ys[galaxy.y]))
+            .collect()
+    }
+
+    /// Sum of the Manhattan distances between every pair of galaxies, each axis on its own.
+    pub fn distance_sum(&self, factor: u64) -> u64 {
+        let (mut xs, mut ys): (Vec<_>, Vec<_>) = self.expanded(factor).into_iter().unzip();
+        pairwise_distance_sum(&mut xs) + pairwise_distance_sum(&mut ys)
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+#[test]
+fn test_pairwise_distance_sum() {
+    let mut values = [7, 1, 4, 4];
+    // 3 + 3 + 6 + 0 + 3 + 3
+    assert_eq!(pairwise_distance_sum(&mut values), 18);
+    assert_eq!(pairwise_distance_sum(&mut []), 0);
+}
+
+#[test]
+fn test_empty_lines() {
+    let image = Image::from(EXAMPLE);
+    assert_eq!(image.empty_lines(), (vec![3, 7], vec![2, 5, 8]));
+}
+
+#[test]
+fn test_expanded() {
+    let image = Image::from(EXAMPLE);
+    let galaxies = image.expanded(2);
+    assert_eq!(galaxies[0], (4, 0));
+    assert_eq!(galaxies[8], (5, 11));
+}
+
+#[test]
+fn test_distance_sum_matches_brute_force() {
+    let image = Image::from(EXAMPLE);
+    for factor in [1, 2, 10, 100] {
+        let galaxies = image.expanded(factor);
+        let mut expected = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                expected += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            }
+        }
+        assert_eq!(image.distance_sum(factor), expected);
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::{BTreeSet, BinaryHeap, HashMap, VecDeque},
+    fmt::Display,
+};
+
+use petgraph::{graph::UnGraph, visit::EdgeRef};
+
+use super::export::GraphExport;
+
+/// Undirected graph with unit edge weights and string labels.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LabelledGraph {
+    labels: Vec<String>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl LabelledGraph {
+    /// Edges listed from both ends are only kept once.
+    pub fn from_adjacency<K, V, I>(adjacency: impl IntoIterator<Item = (K, I)>) -> Self
+    where
+        K: ToString,
+        V: ToString,
+        I: IntoIterator<Item = V>,
+    {
+        let mut indices = HashMap::new();
+        let mut graph = Self::default();
+        let mut edges = BTreeSet::new();
+        for (source, destinations) in adjacency {
+            let source = graph.index_of(&mut indices, source.to_string());
+            for destination in destinations {
+                let destination = graph.index_of(&mut indices, destination.to_string());
+                if source != destination {
+                    edges.insert((source.min(destination), source.max(destination)));
+                }
+            }
+        }
+        graph.edges = edges.into_iter().collect();
+        graph
+    }
+
+    fn index_of(&mut self, indices: &mut HashMap<String, usize>, label: String) -> usize {
+        *indices.entry(label.clone()).or_insert_with(|| {
+            self.labels.push(label);
+            self.labels.len() - 1
+        })
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Undirected graph of the labels, a cut being shown with `with_highlighted`.
+    pub fn export(&self) -> GraphExport {
+        GraphExport::undirected(
+            self.edges
+                .iter()
+                .map(|&(a, b)| (&self.labels[a], [&self.labels[b]])),
+        )
+    }
+
+    fn neighbours(&self) -> Vec<Vec<usize>> {
+        let mut neighbours = vec![vec![]; self.labels.len()];
+        for &(a, b) in &self.edges {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+        neighbours
+    }
+
+    fn cut_from_side(&self, side: &[bool]) -> Cut {
+        let edges = self
+            .edges
+            .iter()
+            .filter(|(a, b)| side[*a] != side[*b])
+            .map(|(a, b)| (self.labels[*a].clone(), self.labels[*b].clone()))
+            .collect();
+        let (inside, outside): (Vec<_>, Vec<_>) = (0..self.labels.len()).partition(|&i| side[i]);
+        let to_labels = |nodes: Vec<usize>| {
+            nodes
+                .into_iter()
+                .map(|i| self.labels[i].clone())
+                .collect::<Vec<_>>()
+        };
+        Cut {
+            edges,
+            partition: (to_labels(inside), to_labels(outside)),
+        }
+    }
+}
+
+impl<N, E> From<&UnGraph<N, E>> for LabelledGraph
+where
+    N: Display,
+{
+    fn from(graph: &UnGraph<N, E>) -> Self {
+        Self {
+            labels: graph.node_weights().map(|n| n.to_string()).collect(),
+            edges: graph
+                .edge_references()
+                .filter(|edge| edge.source() != edge.target())
+                .map(|edge| (edge.source().index(), edge.target().index()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub edges: Vec<(String, String)>,
+    pub partition: (Vec<String>, Vec<String>),
+}
+
+impl Cut {
+    pub fn weight(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn sizes(&self) -> (usize, usize) {
+        (self.partition.0.len(), self.partition.1.len())
+    }
+}
+
+/// Deterministic global minimum cut, `O(V·E·log V)`.
+pub fn stoer_wagner(graph: &LabelledGraph) -> Option<Cut> {
+    let n = graph.node_count();
+    if n < 2 {
+        return None;
+    }
+    let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+    for &(a, b) in &graph.edges {
+        *weights[a].entry(b).or_default() += 1;
+        *weights[b].entry(a).or_default() += 1;
+    }
+    let mut groups = (0..n).map(|i| vec![i]).collect::<Vec<_>>();
+    let mut active = (0..n).collect::<BTreeSet<_>>();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // maximum adjacency ordering, the last two nodes being merged afterwards
+        let start = *active.iter().next().unwrap();
+        let mut keys = vec![0; n];
+        let mut added = vec![false; n];
+        let mut heap = BinaryHeap::from([(0, start)]);
+        let (mut previous, mut last) = (start, start);
+        while let Some((key, node)) = heap.pop() {
+            if added[node] || key != keys[node] {
+                continue;
+            }
+            added[node] = true;
+            (previous, last) = (last, node);
+            for (&neighbour, &weight) in &weights[node] {
+                if !added[neighbour] {
+                    keys[neighbour] += weight;
+                    heap.push((keys[neighbour], neighbour));
+                }
+            }
+        }
+
+        if active.iter().any(|&node| !added[node]) {
+            // disconnected: the nodes reached so far form a zero-weight cut
+            let mut side = vec![true; n];
+            for node in active.iter().filter(|&&node| !added[node]) {
+                for &merged in &groups[*node] {
+                    side[merged] = false;
+                }
+            }
+            return Some(graph.cut_from_side(&side));
+        }
+
+        if best.as_ref().is_none_or(|(weight, _)| keys[last] < *weight) {
+            best = Some((keys[last], groups[last].clone()));
+        }
+
+        let merged = std::mem::take(&mut weights[last]);
+        for (neighbour, weight) in merged {
+            weights[neighbour].remove(&last);
+            if neighbour != previous {
+                *weights[previous].entry(neighbour).or_default() += weight;
+                *weights[neighbour].entry(previous).or_default() += weight;
+            }
+        }
+        let group = std::mem::take(&mut groups[last]);
+        groups[previous].extend(group);
+        active.remove(&last);
+    }
+
+    let (_, group) = best?;
+    let mut side = vec![false; n];
+    for node in group {
+        side[node] = true;
+    }
+    Some(graph.cut_from_side(&side))
+}
+
+// splitmix64, enough randomness for edge contraction without pulling in a crate
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+fn find(parents: &mut [usize], node: usize) -> usize {
+    let mut root = node;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut node = node;
+    while parents[node] != root {
+        let parent = parents[node];
+        parents[node] = root;
+        node = parent;
+    }
+    root
+}
+
+/// Randomized contraction, keeping the smallest cut over `trials` runs.
+/// The same `seed` always yields the same cut.
+pub fn karger(graph: &LabelledGraph, trials: usize, seed: u64) -> Option<Cut> {
+    let n = graph.node_count();
+    if n < 2 {
+        return None;
+    }
+    let mut rng = Rng(seed);
+    let mut best: Option<Cut> = None;
+    for _ in 0..trials {
+        let mut edges = graph.edges.clone();
+        for i in (1..edges.len()).rev() {
+            edges.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+        }
+        let mut parents = (0..n).collect::<Vec<_>>();
+        let mut components = n;
+        for &(a, b) in &edges {
+            if components == 2 {
+                break;
+            }
+            let (a, b) = (find(&mut parents, a), find(&mut parents, b));
+            if a != b {
+                parents[b] = a;
+                components -= 1;
+            }
+        }
+        // on a disconnected graph more components remain, the first one is a zero-weight cut
+        let first = find(&mut parents, 0);
+        let side = (0..n)
+            .map(|node| find(&mut parents, node) == first)
+            .collect::<Vec<_>>();
+        let cut = graph.cut_from_side(&side);
+        if best.as_ref().is_none_or(|b| cut.weight() < b.weight()) {
+            best = Some(cut);
+        }
+    }
+    best
+}
+
+/// Maximum flow between `source` and `sink` with unit capacities, along with the minimum cut
+/// separating them: the source side holds every node still reachable in the residual graph.
+pub fn edmonds_karp(graph: &LabelledGraph, source: &str, sink: &str) -> Option<(usize, Cut)> {
+    let source = graph.labels.iter().position(|l| l == source)?;
+    let sink = graph.labels.iter().position(|l| l == sink)?;
+    if source == sink {
+        return None;
+    }
+    let neighbours = graph.neighbours();
+    let mut capacities: HashMap<(usize, usize), i64> = HashMap::new();
+    for &(a, b) in &graph.edges {
+        *capacities.entry((a, b)).or_default() += 1;
+        *capacities.entry((b, a)).or_default() += 1;
+    }
+    let mut flow: HashMap<(usize, usize), i64> = HashMap::new();
+    let residual = |flow: &HashMap<(usize, usize), i64>, a: usize, b: usize| {
+        capacities.get(&(a, b)).copied().unwrap_or(0) - flow.get(&(a, b)).copied().unwrap_or(0)
+    };
+
+    let mut total = 0;
+    loop {
+        let mut parents = vec![None; graph.node_count()];
+        parents[source] = Some(source);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &neighbours[node] {
+                if parents[next].is_none() && residual(&flow, node, next) > 0 {
+                    parents[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if parents[sink].is_none() {
+            let side = parents.iter().map(|p| p.is_some()).collect::<Vec<_>>();
+            return Some((total, graph.cut_from_side(&side)));
+        }
+
+        let mut node = sink;
+        while node != source {
+            let parent = parents[node].unwrap();
+            *flow.entry((parent, node)).or_default() += 1;
+            *flow.entry((node, parent)).or_default() -= 1;
+            node = parent;
+        }
+        total += 1;
+    }
+}
+
+#[cfg(test)]
+fn two_triangles() -> LabelledGraph {
+    // two triangles linked by a single bridge c - d
+    LabelledGraph::from_adjacency([
+        ("a", vec!["b", "c"]),
+        ("b", vec!["c"]),
+        ("c", vec!["d"]),
+        ("d", vec!["e", "f"]),
+        ("e", vec!["f"]),
+    ])
+}
+
+#[test]
+fn test_stoer_wagner() {
+    let cut = stoer_wagner(&two_triangles()).unwrap();
+    assert_eq!(cut.edges, vec![("c".to_owned(), "d".to_owned())]);
+    assert_eq!(cut.sizes(), (3, 3));
+}
+
+#[test]
+fn test_karger() {
+    let graph = two_triangles();
+    let cut = karger(&graph, 50, 42).unwrap();
+    assert_eq!(cut.weight(), 1);
+    assert_eq!(cut.sizes(), (3, 3));
+    assert_eq!(karger(&graph, 50, 42), Some(cut));
+}
+
+#[test]
+fn test_edmonds_karp() {
+    let graph = two_triangles();
+    let (flow, cut) = edmonds_karp(&graph, "a", "f").unwrap();
+    assert_eq!(flow, 1);
+    assert_eq!(cut.edges, vec![("c".to_owned(), "d".to_owned())]);
+    let (flow, _) = edmonds_karp(&graph, "a", "b").unwrap();
+    assert_eq!(flow, 2);
+}
+
+#[test]
+fn test_from_petgraph() {
+    let mut graph = UnGraph::<&str, ()>::new_undirected();
+    let [a, b, c] = ["a", "b", "c"].map(|label| graph.add_node(label));
+    graph.extend_with_edges([(a, b), (b, c)]);
+    let cut = stoer_wagner(&LabelledGraph::from(&graph)).unwrap();
+    assert_eq!(cut.weight(), 1);
+}
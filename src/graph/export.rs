@@ -0,0 +1,257 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Shape {
+    #[default]
+    Box,
+    Round,
+    Diamond,
+    Circle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphExport {
+    directed: bool,
+    edges: BTreeSet<(String, String)>,
+    nodes: BTreeSet<String>,
+    shapes: HashMap<String, Shape>,
+    labels: HashMap<String, String>,
+    highlighted: HashSet<(String, String)>,
+}
+
+impl GraphExport {
+    fn from_adjacency<K, V, I>(adjacency: impl IntoIterator<Item = (K, I)>, directed: bool) -> Self
+    where
+        K: ToString,
+        V: ToString,
+        I: IntoIterator<Item = V>,
+    {
+        let mut nodes = BTreeSet::new();
+        let mut edges = BTreeSet::new();
+        for (source, destinations) in adjacency {
+            let source = source.to_string();
+            for destination in destinations {
+                let destination = destination.to_string();
+                nodes.insert(destination.clone());
+                edges.insert(edge_key(directed, &source, &destination));
+            }
+            nodes.insert(source);
+        }
+        Self {
+            directed,
+            edges,
+            nodes,
+            shapes: HashMap::new(),
+            labels: HashMap::new(),
+            highlighted: HashSet::new(),
+        }
+    }
+
+    pub fn directed<K, V, I>(adjacency: impl IntoIterator<Item = (K, I)>) -> Self
+    where
+        K: ToString,
+        V: ToString,
+        I: IntoIterator<Item = V>,
+    {
+        Self::from_adjacency(adjacency, true)
+    }
+
+    /// Edges listed from both ends are only exported once.
+    pub fn undirected<K, V, I>(adjacency: impl IntoIterator<Item = (K, I)>) -> Self
+    where
+        K: ToString,
+        V: ToString,
+        I: IntoIterator<Item = V>,
+    {
+        Self::from_adjacency(adjacency, false)
+    }
+
+    pub fn with_shape(mut self, node: &str, shape: Shape) -> Self {
+        self.shapes.insert(node.to_owned(), shape);
+        self
+    }
+
+    pub fn with_label(mut self, node: &str, label: &str) -> Self {
+        self.labels.insert(node.to_owned(), label.to_owned());
+        self
+    }
+
+    pub fn with_highlighted<'a>(
+        mut self,
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        for (source, destination) in edges {
+            self.highlighted
+                .insert(edge_key(self.directed, source, destination));
+        }
+        self
+    }
+
+    fn label<'a>(&'a self, node: &'a str) -> &'a str {
+        self.labels.get(node).map(|l| l.as_str()).unwrap_or(node)
+    }
+
+    fn shape(&self, node: &str) -> Shape {
+        self.shapes.get(node).copied().unwrap_or_default()
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        // node names may hold anything, so mermaid ids are their rank among the nodes
+        let ranks = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(rank, node)| (node.as_str(), rank))
+            .collect::<HashMap<_, _>>();
+        let id = |node: &str| format!("n{}", ranks[node]);
+        let mut output = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let label = self.label(node).replace('"', "#quot;");
+            let shape = self.shape(node);
+            let node = id(node);
+            let declaration = match shape {
+                Shape::Box => format!("{node}[\"{label}\"]"),
+                Shape::Round => format!("{node}(\"{label}\")"),
+                Shape::Diamond => format!("{node}{{\"{label}\"}}"),
+                Shape::Circle => format!("{node}((\"{label}\"))"),
+            };
+            output.push_str(&format!("    {declaration}\n"));
+        }
+        let arrow = if self.directed { "-->" } else { "---" };
+        let mut highlighted = vec![];
+        for (i, (source, destination)) in self.edges.iter().enumerate() {
+            let (a, b) = (id(source), id(destination));
+            output.push_str(&format!("    {a} {arrow} {b}\n"));
+            if self
+                .highlighted
+                .contains(&(source.clone(), destination.clone()))
+            {
+                highlighted.push(i.to_string());
+            }
+        }
+        if !highlighted.is_empty() {
+            output.push_str(&format!(
+                "    linkStyle {} stroke:red,stroke-width:3px\n",
+                highlighted.join(",")
+            ));
+        }
+        output
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut output = format!("{kind} {{\n");
+        for node in &self.nodes {
+            let shape = match self.shape(node) {
+                Shape::Box => "box",
+                Shape::Round => "ellipse",
+                Shape::Diamond => "diamond",
+                Shape::Circle => "circle",
+            };
+            output.push_str(&format!(
+                "    \"{}\" [label=\"{}\", shape={shape}];\n",
+                dot_escape(node),
+                dot_escape(self.label(node))
+            ));
+        }
+        for (source, destination) in &self.edges {
+            let style = if self
+                .highlighted
+                .contains(&(source.clone(), destination.clone()))
+            {
+                " [color=red, penwidth=3]"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "    \"{}\" {arrow} \"{}\"{style};\n",
+                dot_escape(source),
+                dot_escape(destination)
+            ));
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+fn edge_key(directed: bool, source: &str, destination: &str) -> (String, String) {
+    if directed || source <= destination {
+        (source.to_owned(), destination.to_owned())
+    } else {
+        (destination.to_owned(), source.to_owned())
+    }
+}
+
+// inside a quoted DOT string, only quotes and backslashes need escaping
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[test]
+fn test_mermaid() {
+    use std::collections::BTreeMap;
+
+    let adjacency = BTreeMap::from([("a", vec!["b", "c"]), ("b", vec!["c"])]);
+    let export = GraphExport::directed(&adjacency)
+        .with_shape("b", Shape::Diamond)
+        .with_label("b", "&b")
+        .with_highlighted([("b", "c")]);
+    assert_eq!(
+        export.to_mermaid(),
+        "flowchart LR
+    n0[\"a\"]
+    n1{\"&b\"}
+    n2[\"c\"]
+    n0 --> n1
+    n0 --> n2
+    n1 --> n2
+    linkStyle 2 stroke:red,stroke-width:3px
+"
+    );
+}
+
+#[test]
+fn test_dot() {
+    let adjacency = HashMap::from([("a", HashSet::from(["b"])), ("b", HashSet::from(["a"]))]);
+    let export = GraphExport::undirected(&adjacency).with_highlighted([("b", "a")]);
+    assert_eq!(
+        export.to_dot(),
+        "graph {
+    \"a\" [label=\"a\", shape=box];
+    \"b\" [label=\"b\", shape=box];
+    \"a\" -- \"b\" [color=red, penwidth=3];
+}
+"
+    );
+}
+
+#[test]
+fn test_names_needing_escapes() {
+    let adjacency = [("say \"hi\"", vec!["end", "a-b c"])];
+    let export = GraphExport::directed(adjacency);
+    assert_eq!(
+        export.to_mermaid(),
+        "flowchart LR
+    n0[\"a-b c\"]
+    n1[\"end\"]
+    n2[\"say #quot;hi#quot;\"]
+    n2 --> n0
+    n2 --> n1
+"
+    );
+    assert_eq!(
+        export.to_dot(),
+        r#"digraph {
+    "a-b c" [label="a-b c", shape=box];
+    "end" [label="end", shape=box];
+    "say \"hi\"" [label="say \"hi\"", shape=box];
+    "say \"hi\"" -> "a-b c";
+    "say \"hi\"" -> "end";
+}
+"#
+    );
+}
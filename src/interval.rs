@@ -0,0 +1,311 @@
+use std::ops::Range;
+
+/// Half-open range of integers `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl From<Range<u64>> for Interval {
+    fn from(range: Range<u64>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl Interval {
+    pub fn new(start: u64, end: u64) -> Self {
+        Interval {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    /// Saturates at `u64::MAX` instead of overflowing.
+    pub fn with_len(start: u64, len: u64) -> Self {
+        Interval::new(start, start.saturating_add(len))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let interval = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    /// Splits into the values lower than `at` and the others.
+    pub fn split_at(&self, at: u64) -> (Option<Interval>, Option<Interval>) {
+        let at = at.clamp(self.start, self.end);
+        let before = Interval::new(self.start, at);
+        let after = Interval::new(at, self.end);
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    /// The parts of `self` below and above `other`. When they do not overlap, `self` is
+    /// entirely on the side opposite to `other`.
+    pub fn subtract(&self, other: &Interval) -> (Option<Interval>, Option<Interval>) {
+        (self.split_at(other.start).0, self.split_at(other.end).1)
+    }
+
+    /// `None` when the shifted interval would leave the `u64` range.
+    pub fn shift(&self, offset: i128) -> Option<Interval> {
+        let shift = |value: u64| u64::try_from(i128::from(value) + offset).ok();
+        Some(Interval::new(shift(self.start)?, shift(self.end)?))
+    }
+}
+
+/// Sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut intervals = iter
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort();
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+}
+
+impl IntervalSet {
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersect(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(interval) = a.intersect(&b) {
+                result.push(interval);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        result.into_iter().collect()
+    }
+
+    pub fn subtract(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = vec![];
+        for interval in self.iter() {
+            let mut remaining = Some(*interval);
+            for removed in other.iter() {
+                let Some(current) = remaining else {
+                    break;
+                };
+                let (below, above) = current.subtract(removed);
+                result.extend(below);
+                remaining = above;
+            }
+            result.extend(remaining);
+        }
+        result.into_iter().collect()
+    }
+
+    /// `None` when an interval would leave the `u64` range.
+    pub fn shift(&self, offset: i128) -> Option<IntervalSet> {
+        self.iter().map(|interval| interval.shift(offset)).collect()
+    }
+}
+
+/// Piecewise-linear mapping: values inside a source interval are moved by its offset,
+/// every other value maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RangeMap {
+    /// Each source interval with the value its start maps to.
+    rules: Vec<(Interval, u64)>,
+}
+
+impl RangeMap {
+    pub fn insert(&mut self, source: Interval, destination_start: u64) {
+        self.rules.push((source, destination_start));
+    }
+
+    /// `None` when the image does not fit in a `u64`.
+    pub fn map_value(&self, value: u64) -> Option<u64> {
+        match self.rules.iter().find(|(source, _)| source.contains(value)) {
+            Some((source, destination)) => destination.checked_add(value - source.start),
+            None => Some(value),
+        }
+    }
+
+    /// Images of every value in `set`, in one pass over the rules. `None` when an image
+    /// does not fit in a `u64`.
+    pub fn map_set(&self, set: &IntervalSet) -> Option<IntervalSet> {
+        let mut remaining = set.clone();
+        let mut mapped = IntervalSet::default();
+        for (source, destination) in &self.rules {
+            let offset = i128::from(*destination) - i128::from(source.start);
+            let source = IntervalSet::from_iter([*source]);
+            mapped = mapped.union(&remaining.intersect(&source).shift(offset)?);
+            remaining = remaining.subtract(&source);
+        }
+        Some(mapped.union(&remaining))
+    }
+}
+
+#[test]
+fn test_interval() {
+    let interval = Interval::from(10..20);
+    assert_eq!(interval.len(), 10);
+    assert_eq!(
+        interval.intersect(&Interval::new(15, 30)),
+        Some(Interval::new(15, 20))
+    );
+    assert_eq!(interval.intersect(&Interval::new(20, 30)), None);
+    assert_eq!(
+        interval.split_at(12),
+        (Some(Interval::new(10, 12)), Some(Interval::new(12, 20)))
+    );
+    assert_eq!(
+        interval.subtract(&Interval::new(12, 14)),
+        (Some(Interval::new(10, 12)), Some(Interval::new(14, 20)))
+    );
+    assert_eq!(interval.subtract(&Interval::new(0, 30)), (None, None));
+    assert_eq!(
+        interval.subtract(&Interval::new(0, 5)),
+        (None, Some(interval))
+    );
+    assert_eq!(
+        interval.subtract(&Interval::new(25, 30)),
+        (Some(interval), None)
+    );
+    assert_eq!(
+        Interval::with_len(u64::MAX - 1, 5),
+        Interval::new(u64::MAX - 1, u64::MAX)
+    );
+}
+
+#[test]
+fn test_interval_set() {
+    let a = IntervalSet::from_iter([
+        Interval::new(0, 5),
+        Interval::new(5, 8),
+        Interval::new(10, 12),
+    ]);
+    assert_eq!(a.iter().count(), 2);
+    assert_eq!(a.len(), 10);
+    assert!(a.contains(7) && !a.contains(8) && a.contains(10));
+
+    let b = IntervalSet::from_iter([Interval::new(3, 11)]);
+    assert_eq!(
+        a.intersect(&b),
+        IntervalSet::from_iter([Interval::new(3, 8), Interval::new(10, 11)])
+    );
+    assert_eq!(
+        a.subtract(&b),
+        IntervalSet::from_iter([Interval::new(0, 3), Interval::new(11, 12)])
+    );
+    assert_eq!(a.union(&b), IntervalSet::from_iter([Interval::new(0, 12)]));
+}
+
+#[test]
+fn test_subtract_several_intervals() {
+    let a = IntervalSet::from_iter([Interval::new(10, 20)]);
+    let removed = IntervalSet::from_iter([
+        Interval::new(0, 5),
+        Interval::new(12, 14),
+        Interval::new(16, 17),
+        Interval::new(30, 40),
+    ]);
+    assert_eq!(
+        a.subtract(&removed),
+        IntervalSet::from_iter([
+            Interval::new(10, 12),
+            Interval::new(14, 16),
+            Interval::new(17, 20)
+        ])
+    );
+}
+
+#[test]
+fn test_range_map() {
+    let mut map = RangeMap::default();
+    map.insert(Interval::with_len(98, 2), 50);
+    map.insert(Interval::with_len(50, 48), 52);
+    assert_eq!(map.map_value(10), Some(10));
+    assert_eq!(map.map_value(98), Some(50));
+    assert_eq!(map.map_value(53), Some(55));
+
+    let mapped = map
+        .map_set(&IntervalSet::from_iter([Interval::new(45, 100)]))
+        .unwrap();
+    assert_eq!(
+        mapped,
+        IntervalSet::from_iter([
+            Interval::new(45, 50),
+            Interval::new(50, 52),
+            Interval::new(52, 100)
+        ])
+    );
+    assert_eq!(mapped.len(), 55);
+}
+
+#[test]
+fn test_range_map_near_the_end() {
+    let mut map = RangeMap::default();
+    map.insert(Interval::new(u64::MAX - 10, u64::MAX), 0);
+    map.insert(Interval::new(0, 10), u64::MAX - 5);
+    assert_eq!(map.map_value(u64::MAX - 1), Some(9));
+    assert_eq!(map.map_value(5), Some(u64::MAX));
+    assert_eq!(map.map_value(6), None);
+
+    let set = |start, end| IntervalSet::from_iter([Interval::new(start, end)]);
+    assert_eq!(map.map_set(&set(u64::MAX - 10, u64::MAX)), Some(set(0, 10)));
+    assert_eq!(map.map_set(&set(0, 5)), Some(set(u64::MAX - 5, u64::MAX)));
+    assert_eq!(map.map_set(&set(0, 6)), None);
+}
@@ -1,4 +1,24 @@
+pub mod beam;
+pub mod bricks;
+pub mod buckets;
+pub mod cards;
+pub mod cycle;
 pub mod direction;
+pub mod galaxy;
+pub mod graph;
+pub mod interval;
 pub mod map;
+pub mod motion;
+pub mod nonogram;
+pub mod parse;
+pub mod pipes;
+pub mod platform;
 pub mod point3d;
+pub mod polygon;
+pub mod pulse;
+pub mod quadratic;
+pub mod scanner;
+pub mod sequence;
 pub mod template;
+pub mod walker;
+pub mod workflow;
@@ -1,5 +1,10 @@
 use std::{fmt::Display, str::FromStr};
 
+pub mod numbers;
+pub mod regions;
+pub mod render;
+pub mod symmetry;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct Position {
     pub x: usize,
@@ -56,7 +61,7 @@ impl From<(usize, usize)> for Position {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Map<T> {
     pub map: Vec<Vec<T>>,
 }
@@ -175,7 +180,7 @@ where
 }
 
 // from https://stackoverflow.com/a/64499219/10558013
-pub fn transpose<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
+pub fn transpose<T>(v: &[Vec<T>]) -> Vec<Vec<T>>
 where
     T: Clone,
 {
@@ -186,16 +191,17 @@ where
 }
 
 impl Map<char> {
-    pub fn get_numbers_and_position(&self) -> Vec<(u32, Position)> {
-        let mut result: Vec<(u32, Position)> = Vec::new();
-
-        for (y, row) in self.map.iter().enumerate() {
-            let numbers_in_row = get_numbers_from_line(row.clone());
-            for (x, number) in numbers_in_row {
-                result.push((number, Position { x, y }));
-            }
-        }
-        result
+    pub fn get_numbers_and_position(&self) -> Vec<(u64, Position)> {
+        self.number_spans()
+            .into_iter()
+            .map(|span| {
+                let position = Position {
+                    x: span.columns.start,
+                    y: span.row,
+                };
+                (span.value, position)
+            })
+            .collect()
     }
 }
 
@@ -221,33 +227,28 @@ fn test_get_adjacents() {
     assert!(d.contains(&('8', Position { x: 1, y: 2 })));
 }
 
-fn get_numbers_from_line(line: Vec<char>) -> Vec<(usize, u32)> {
-    line.iter()
-        .enumerate()
-        .filter_map(|(position, c)| {
-            if !c.is_ascii_digit() {
-                return None;
-            }
-            if position != 0 {
-                let previous_char = line.get(position - 1).unwrap();
-                if previous_char.is_ascii_digit() {
-                    return None;
-                }
-            }
-            let number: String = line
-                .iter()
-                .skip(position)
-                .take_while(|c| c.is_ascii_digit())
-                .collect();
-            Some((position, number.parse::<u32>().unwrap()))
-        })
-        .collect()
+#[test]
+fn test_map_as_cycle_state() {
+    let rotate = |map: &Map<char>| {
+        let mut map = map.clone();
+        map.map.rotate_left(1);
+        map
+    };
+    let map: Map<char> = Map::from("a\nb\nc");
+    assert_eq!(
+        crate::cycle::state_after(map.clone(), rotate, 1_000_000_000),
+        rotate(&map)
+    );
 }
 
 #[test]
-fn test_get_numbers_from_line() {
+fn test_get_numbers_and_position() {
+    let map: Map<char> = Map::from("467..114..");
     assert_eq!(
-        get_numbers_from_line("467..114..".chars().collect()),
-        vec![(0, 467), (5, 114)]
+        map.get_numbers_and_position(),
+        vec![
+            (467, Position { x: 0, y: 0 }),
+            (114, Position { x: 5, y: 0 })
+        ]
     );
 }
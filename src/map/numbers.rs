@@ -0,0 +1,140 @@
+use std::ops::Range;
+
+use super::{Map, Position};
+
+/// A number written left to right on `row`, its digits covering `columns`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberSpan {
+    pub value: u64,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+/// Finds the numbers next to a cell, diagonals included, without scanning them all.
+#[derive(Debug, Clone)]
+pub struct SpanIndex {
+    spans: Vec<NumberSpan>,
+    /// Span written over each cell, if any.
+    cells: Vec<Vec<Option<usize>>>,
+}
+
+impl Map<char> {
+    /// Runs of digits too long for a `u64` are skipped.
+    pub fn number_spans(&self) -> Vec<NumberSpan> {
+        let mut spans = vec![];
+        for (row, line) in self.map.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                let length = line[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+                if length == 0 {
+                    x += 1;
+                    continue;
+                }
+                let digits = line[x..x + length].iter().collect::<String>();
+                if let Ok(value) = digits.parse() {
+                    spans.push(NumberSpan {
+                        value,
+                        row,
+                        columns: x..x + length,
+                    });
+                }
+                x += length;
+            }
+        }
+        spans
+    }
+
+    pub fn span_index(&self) -> SpanIndex {
+        let spans = self.number_spans();
+        let mut cells = self
+            .map
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<_>>();
+        for (index, span) in spans.iter().enumerate() {
+            for x in span.columns.clone() {
+                cells[span.row][x] = Some(index);
+            }
+        }
+        SpanIndex { spans, cells }
+    }
+}
+
+impl SpanIndex {
+    pub fn spans(&self) -> &[NumberSpan] {
+        &self.spans
+    }
+
+    /// Every span touching `position`, each once, in reading order.
+    pub fn adjacent_to(&self, position: Position) -> Vec<&NumberSpan> {
+        let mut found = vec![];
+        for y in position.y.saturating_sub(1)..=position.y + 1 {
+            for x in position.x.saturating_sub(1)..=position.x + 1 {
+                let span = self
+                    .cells
+                    .get(y)
+                    .and_then(|row| row.get(x))
+                    .copied()
+                    .flatten();
+                if let Some(span) = span.filter(|span| !found.contains(span)) {
+                    found.push(span);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.into_iter().map(|span| &self.spans[span]).collect()
+    }
+}
+
+#[test]
+fn test_number_spans() {
+    let map: Map<char> = Map::from("467..114..\n...*......\n..35..633.");
+    let spans = map.number_spans();
+    assert_eq!(
+        spans[..2],
+        [
+            NumberSpan {
+                value: 467,
+                row: 0,
+                columns: 0..3
+            },
+            NumberSpan {
+                value: 114,
+                row: 0,
+                columns: 5..8
+            }
+        ]
+    );
+    assert_eq!(spans.len(), 4);
+    assert_eq!(spans[3].columns, 6..9);
+
+    let map: Map<char> = Map::from("12345678901.\n1.123456789012345678901");
+    let values = map
+        .number_spans()
+        .iter()
+        .map(|span| span.value)
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![12345678901, 1]);
+}
+
+#[test]
+fn test_adjacent_spans() {
+    let map: Map<char> = Map::from("467..114..\n...*......\n..35..633.");
+    let index = map.span_index();
+    let values = |position| {
+        index
+            .adjacent_to(position)
+            .iter()
+            .map(|span| span.value)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(values(Position::from((3, 1))), vec![467, 35]);
+    assert_eq!(values(Position::from((9, 1))), vec![633]);
+    assert_eq!(values(Position::from((0, 2))), vec![]);
+    // a cell inside a number sees that number once
+    assert_eq!(values(Position::from((1, 0))), vec![467]);
+
+    // rows longer than the first one are indexed too
+    let index = Map::from(".\n..23*").span_index();
+    assert_eq!(index.adjacent_to(Position::from((4, 1)))[0].value, 23);
+}
@@ -0,0 +1,260 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{Map, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub positions: Vec<Position>,
+    pub perimeter: usize,
+    pub bounding_box: (Position, Position),
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.positions.contains(position)
+    }
+}
+
+impl<T> Map<T>
+where
+    T: Copy,
+{
+    pub fn width(&self) -> usize {
+        self.map.first().map(|row| row.len()).unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn get_neighbours(&self, position: Position, connectivity: Connectivity) -> Vec<Position> {
+        connectivity
+            .offsets()
+            .iter()
+            .filter_map(|(dx, dy)| {
+                let x = position.x.checked_add_signed(*dx)?;
+                let y = position.y.checked_add_signed(*dy)?;
+                let next = Position { x, y };
+                (!self.is_out_of_bounds(next)).then_some(next)
+            })
+            .collect()
+    }
+
+    /// Breadth-first flood fill from every seed through the cells matching `is_open`.
+    /// Returns each reached position with its distance in steps to the closest seed.
+    pub fn flood_fill(
+        &self,
+        seeds: &[Position],
+        connectivity: Connectivity,
+        is_open: impl Fn(T) -> bool,
+    ) -> HashMap<Position, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        for &seed in seeds {
+            if !self.is_out_of_bounds(seed) && distances.insert(seed, 0).is_none() {
+                queue.push_back(seed);
+            }
+        }
+
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[&position];
+            for next in self.get_neighbours(position, connectivity) {
+                if is_open(self.get(next)) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Labels every connected component of cells matching `is_member`, in reading order.
+    pub fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        is_member: impl Fn(T) -> bool,
+    ) -> Vec<Region> {
+        let mut visited = HashSet::new();
+        let mut regions = vec![];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let seed = Position { x, y };
+                if visited.contains(&seed) || !is_member(self.get(seed)) {
+                    continue;
+                }
+                let mut positions = self
+                    .flood_fill(&[seed], connectivity, &is_member)
+                    .into_keys()
+                    .collect::<Vec<_>>();
+                positions.sort_by_key(|p| (p.y, p.x));
+                visited.extend(positions.iter().copied());
+                regions.push(self.build_region(positions));
+            }
+        }
+        regions
+    }
+
+    fn build_region(&self, positions: Vec<Position>) -> Region {
+        let members = positions.iter().copied().collect::<HashSet<_>>();
+        let perimeter = positions
+            .iter()
+            .map(|&position| {
+                let inner_sides = self
+                    .get_neighbours(position, Connectivity::Four)
+                    .iter()
+                    .filter(|neighbour| members.contains(neighbour))
+                    .count();
+                4 - inner_sides
+            })
+            .sum();
+        let min = Position {
+            x: positions.iter().map(|p| p.x).min().unwrap(),
+            y: positions.iter().map(|p| p.y).min().unwrap(),
+        };
+        let max = Position {
+            x: positions.iter().map(|p| p.x).max().unwrap(),
+            y: positions.iter().map(|p| p.y).max().unwrap(),
+        };
+        Region {
+            positions,
+            perimeter,
+            bounding_box: (min, max),
+        }
+    }
+
+    /// Every cell strictly inside the closed `polygon`, cells on its boundary excluded.
+    pub fn enclosed_positions(&self, polygon: &[Position]) -> Vec<Position> {
+        let edges = polygon_edges(polygon);
+        let mut result = vec![];
+        for y in 0..self.height() {
+            let row_edges = edges
+                .iter()
+                .filter(|(a, b)| a.y.min(b.y) <= y && y <= a.y.max(b.y))
+                .collect::<Vec<_>>();
+            if row_edges.is_empty() {
+                continue;
+            }
+            for x in 0..self.width() {
+                let position = Position { x, y };
+                if is_inside(&position, row_edges.iter().copied()) {
+                    result.push(position);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Whether `position` lies strictly inside the closed `polygon`, using the crossing number.
+pub fn is_enclosed_by(position: &Position, polygon: &[Position]) -> bool {
+    is_inside(position, polygon_edges(polygon).iter())
+}
+
+fn polygon_edges(polygon: &[Position]) -> Vec<(Position, Position)> {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+        .filter(|(a, b)| a != b)
+        .collect()
+}
+
+fn is_inside<'a>(
+    position: &Position,
+    edges: impl Iterator<Item = &'a (Position, Position)>,
+) -> bool {
+    let (px, py) = (position.x as i64, position.y as i64);
+    let mut inside = false;
+    for (a, b) in edges {
+        let (ax, ay) = (a.x as i64, a.y as i64);
+        let (bx, by) = (b.x as i64, b.y as i64);
+        let cross = (bx - ax) * (py - ay) - (px - ax) * (by - ay);
+        let on_segment = cross == 0
+            && ax.min(bx) <= px
+            && px <= ax.max(bx)
+            && ay.min(by) <= py
+            && py <= ay.max(by);
+        if on_segment {
+            return false;
+        }
+        if (ay > py) != (by > py) && (cross > 0) == (by > ay) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[test]
+fn test_flood_fill() {
+    let map: Map<char> = Map::from("..#\n.##\n#..");
+    let distances = map.flood_fill(&[Position { x: 0, y: 0 }], Connectivity::Four, |c| c == '.');
+    assert_eq!(distances.len(), 3);
+    assert_eq!(distances[&Position { x: 0, y: 1 }], 1);
+
+    let distances = map.flood_fill(&[Position { x: 0, y: 1 }], Connectivity::Eight, |c| {
+        c == '.'
+    });
+    assert_eq!(distances[&Position { x: 1, y: 2 }], 1);
+    assert_eq!(distances[&Position { x: 2, y: 2 }], 2);
+}
+
+#[test]
+fn test_label_regions() {
+    let map: Map<char> = Map::from("AAB\nABB\nCCB");
+    let regions = map.label_regions(Connectivity::Four, |c| c == 'B');
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].area(), 4);
+    assert_eq!(regions[0].perimeter, 10);
+    assert_eq!(
+        regions[0].bounding_box,
+        (Position { x: 1, y: 0 }, Position { x: 2, y: 2 })
+    );
+
+    let map: Map<char> = Map::from("#.#\n.#.\n#.#");
+    assert_eq!(map.label_regions(Connectivity::Four, |c| c == '#').len(), 5);
+    assert_eq!(
+        map.label_regions(Connectivity::Eight, |c| c == '#').len(),
+        1
+    );
+}
+
+#[test]
+fn test_enclosed_by_loop() {
+    let polygon = [(0, 0), (4, 0), (4, 3), (0, 3)]
+        .map(Position::from)
+        .to_vec();
+    assert!(is_enclosed_by(&Position { x: 2, y: 1 }, &polygon));
+    assert!(!is_enclosed_by(&Position { x: 4, y: 1 }, &polygon));
+    assert!(!is_enclosed_by(&Position { x: 5, y: 1 }, &polygon));
+
+    let map: Map<char> = Map::from(".....\n.....\n.....\n.....");
+    assert_eq!(map.enclosed_positions(&polygon).len(), 6);
+}
@@ -0,0 +1,184 @@
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::template::ANSI_RESET;
+
+use super::{Map, Position};
+
+pub const ANSI_HIGHLIGHT: &str = "\x1b[1;33m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const YELLOW: Rgb = Rgb(255, 204, 0);
+}
+
+impl<T> Map<T>
+where
+    T: Copy,
+{
+    /// Renders one line per row, wrapping highlighted positions in ANSI colors.
+    pub fn render(&self, to_char: impl Fn(T) -> char, highlighted: &HashSet<Position>) -> String {
+        let mut output = String::new();
+        for (y, row) in self.map.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                let c = to_char(tile);
+                if highlighted.contains(&Position { x, y }) {
+                    output.push_str(&format!("{ANSI_HIGHLIGHT}{c}{ANSI_RESET}"));
+                } else {
+                    output.push(c);
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Rasterizes the map, each cell becoming a `scale` × `scale` square of pixels.
+    pub fn to_image(&self, scale: usize, to_color: impl Fn(Position, T) -> Rgb) -> Image {
+        let width = self.width() * scale;
+        let height = self.height() * scale;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let position = Position {
+                    x: x / scale,
+                    y: y / scale,
+                };
+                pixels.push(to_color(position, self.get(position)));
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+impl Display for Map<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|c| c, &HashSet::new()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+            .collect()
+    }
+
+    /// Binary PPM (`P6`), readable by most image viewers without any decoder.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut output = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        output.extend(self.rgb_bytes());
+        output
+    }
+
+    pub fn encode_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb_bytes())?;
+        Ok(())
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        File::create(path)?.write_all(&self.to_ppm())
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.encode_png(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Encodes the frames as a looping animated GIF, `delay` being in hundredths of a second.
+pub fn encode_gif(frames: &[Image], delay: u16, writer: impl Write) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames"));
+    };
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let (width, height) = match (u16::try_from(first.width), u16::try_from(first.height)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => return Err(invalid("frames too large for a GIF")),
+    };
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for image in frames {
+        if (image.width, image.height) != (first.width, first.height) {
+            return Err(invalid("frames of different sizes"));
+        }
+        let mut frame = gif::Frame::from_rgb(width, height, &image.rgb_bytes());
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+pub fn save_gif(frames: &[Image], delay: u16, path: impl AsRef<Path>) -> io::Result<()> {
+    encode_gif(frames, delay, BufWriter::new(File::create(path)?))
+}
+
+#[test]
+fn test_render() {
+    let map: Map<char> = Map::from("#.\n.#");
+    assert_eq!(map.to_string(), "#.\n.#\n");
+
+    let highlighted = HashSet::from([Position { x: 1, y: 0 }]);
+    assert_eq!(
+        map.render(|c| c, &highlighted),
+        format!("#{ANSI_HIGHLIGHT}.{ANSI_RESET}\n.#\n")
+    );
+}
+
+#[test]
+fn test_image_export() {
+    let map: Map<char> = Map::from("#.\n.#");
+    let image = map.to_image(2, |_, c| if c == '#' { Rgb::WHITE } else { Rgb::BLACK });
+    assert_eq!((image.width, image.height), (4, 4));
+    assert_eq!(image.pixels[5], Rgb::WHITE);
+    assert_eq!(image.pixels[2], Rgb::BLACK);
+
+    let ppm = image.to_ppm();
+    assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+    assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+
+    let mut png = vec![];
+    image.encode_png(&mut png).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let mut gif = vec![];
+    encode_gif(&[image.clone(), image.clone()], 10, &mut gif).unwrap();
+    assert!(gif.starts_with(b"GIF89a"));
+
+    let smaller = Map::from("#").to_image(2, |_, _: char| Rgb::WHITE);
+    let error = encode_gif(&[image, smaller], 10, &mut vec![]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    let huge = Image {
+        width: 70_000,
+        height: 1,
+        pixels: vec![Rgb::BLACK; 70_000],
+    };
+    let error = encode_gif(&[huge], 10, &mut vec![]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
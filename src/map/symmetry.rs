@@ -0,0 +1,273 @@
+use super::{Map, Position};
+
+/// Orientation of the mirror line: `Horizontal` lies between two rows, `Vertical` between two columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// Number of rows above (or columns left of) the mirror line.
+    pub offset: usize,
+    /// Pairs of mirrored cells whose values differ.
+    pub differences: Vec<(Position, Position)>,
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => self.offset * 100,
+            Axis::Vertical => self.offset,
+        }
+    }
+}
+
+type Matrix = [[i64; 2]; 2];
+
+const IDENTITY: Matrix = [[1, 0], [0, 1]];
+const TRANSPOSE: Matrix = [[0, 1], [1, 0]];
+const ROTATE_CLOCKWISE: Matrix = [[0, 1], [-1, 0]];
+const ROTATE_COUNTER_CLOCKWISE: Matrix = [[0, -1], [1, 0]];
+const ROTATE_HALF: Matrix = [[-1, 0], [0, -1]];
+const FLIP_HORIZONTAL: Matrix = [[-1, 0], [0, 1]];
+const FLIP_VERTICAL: Matrix = [[1, 0], [0, -1]];
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    [
+        [
+            a[0][0] * b[0][0] + a[0][1] * b[1][0],
+            a[0][0] * b[0][1] + a[0][1] * b[1][1],
+        ],
+        [
+            a[1][0] * b[0][0] + a[1][1] * b[1][0],
+            a[1][0] * b[0][1] + a[1][1] * b[1][1],
+        ],
+    ]
+}
+
+/// A rotated and/or flipped view over a map that reads through to the underlying cells
+/// without copying them.
+#[derive(Debug, Clone, Copy)]
+pub struct MapView<'a, T> {
+    map: &'a Map<T>,
+    // maps centered view coordinates onto centered map coordinates
+    matrix: Matrix,
+}
+
+impl<'a, T> MapView<'a, T>
+where
+    T: Copy,
+{
+    fn is_transposed(&self) -> bool {
+        self.matrix[0][0] == 0
+    }
+
+    pub fn width(&self) -> usize {
+        if self.is_transposed() {
+            self.map.height()
+        } else {
+            self.map.width()
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.is_transposed() {
+            self.map.width()
+        } else {
+            self.map.height()
+        }
+    }
+
+    /// Position in the underlying map of the cell shown at `position` in the view.
+    pub fn source_position(&self, position: Position) -> Position {
+        // doubled so that the center of even-sized grids stays integral
+        let u = 2 * position.x as i64 - (self.width() as i64 - 1);
+        let v = 2 * position.y as i64 - (self.height() as i64 - 1);
+        let su = self.matrix[0][0] * u + self.matrix[0][1] * v;
+        let sv = self.matrix[1][0] * u + self.matrix[1][1] * v;
+        Position {
+            x: ((su + self.map.width() as i64 - 1) / 2) as usize,
+            y: ((sv + self.map.height() as i64 - 1) / 2) as usize,
+        }
+    }
+
+    pub fn get(&self, position: Position) -> T {
+        self.map.get(self.source_position(position))
+    }
+
+    fn then(&self, matrix: &Matrix) -> Self {
+        Self {
+            map: self.map,
+            matrix: multiply(&self.matrix, matrix),
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.then(&ROTATE_CLOCKWISE)
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        self.then(&ROTATE_COUNTER_CLOCKWISE)
+    }
+
+    pub fn rotate_half(&self) -> Self {
+        self.then(&ROTATE_HALF)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.then(&FLIP_HORIZONTAL)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.then(&FLIP_VERTICAL)
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.then(&TRANSPOSE)
+    }
+
+    pub fn to_map(&self) -> Map<T> {
+        let map = (0..self.height())
+            .map(|y| {
+                (0..self.width())
+                    .map(|x| self.get(Position { x, y }))
+                    .collect()
+            })
+            .collect();
+        Map { map }
+    }
+
+    /// Mirror lines between rows with at most `tolerance` differing cell pairs,
+    /// positions being reported in the underlying map.
+    pub fn horizontal_reflections(
+        &self,
+        tolerance: usize,
+    ) -> Vec<(usize, Vec<(Position, Position)>)>
+    where
+        T: PartialEq,
+    {
+        (1..self.height())
+            .filter_map(|offset| {
+                let mut differences = vec![];
+                for distance in 0..offset.min(self.height() - offset) {
+                    let (above, below) = (offset - 1 - distance, offset + distance);
+                    for x in 0..self.width() {
+                        let a = Position { x, y: above };
+                        let b = Position { x, y: below };
+                        if self.get(a) != self.get(b) {
+                            differences.push((self.source_position(a), self.source_position(b)));
+                            if differences.len() > tolerance {
+                                return None;
+                            }
+                        }
+                    }
+                }
+                Some((offset, differences))
+            })
+            .collect()
+    }
+}
+
+impl<T> Map<T>
+where
+    T: Copy,
+{
+    pub fn view(&self) -> MapView<'_, T> {
+        MapView {
+            map: self,
+            matrix: IDENTITY,
+        }
+    }
+
+    pub fn rotated_clockwise(&self) -> MapView<'_, T> {
+        self.view().rotate_clockwise()
+    }
+
+    pub fn rotated_counter_clockwise(&self) -> MapView<'_, T> {
+        self.view().rotate_counter_clockwise()
+    }
+
+    pub fn flipped_horizontal(&self) -> MapView<'_, T> {
+        self.view().flip_horizontal()
+    }
+
+    pub fn flipped_vertical(&self) -> MapView<'_, T> {
+        self.view().flip_vertical()
+    }
+
+    /// Every mirror line, horizontal ones first, allowing up to `tolerance` differing cell pairs.
+    pub fn reflections(&self, tolerance: usize) -> Vec<Reflection>
+    where
+        T: PartialEq,
+    {
+        let horizontal = self
+            .view()
+            .horizontal_reflections(tolerance)
+            .into_iter()
+            .map(|(offset, differences)| Reflection {
+                axis: Axis::Horizontal,
+                offset,
+                differences,
+            });
+        let vertical = self
+            .view()
+            .transpose()
+            .horizontal_reflections(tolerance)
+            .into_iter()
+            .map(|(offset, differences)| Reflection {
+                axis: Axis::Vertical,
+                offset,
+                differences,
+            });
+        horizontal.chain(vertical).collect()
+    }
+}
+
+#[test]
+fn test_views() {
+    let map: Map<char> = Map::from("abc\ndef");
+    let to_string = |map: Map<char>| map.to_string();
+    assert_eq!(to_string(map.view().to_map()), "abc\ndef\n");
+    assert_eq!(to_string(map.rotated_clockwise().to_map()), "da\neb\nfc\n");
+    assert_eq!(
+        to_string(map.rotated_counter_clockwise().to_map()),
+        "cf\nbe\nad\n"
+    );
+    assert_eq!(to_string(map.view().rotate_half().to_map()), "fed\ncba\n");
+    assert_eq!(to_string(map.flipped_horizontal().to_map()), "cba\nfed\n");
+    assert_eq!(to_string(map.flipped_vertical().to_map()), "def\nabc\n");
+    assert_eq!(to_string(map.view().transpose().to_map()), "ad\nbe\ncf\n");
+    assert_eq!(
+        to_string(map.rotated_clockwise().rotate_clockwise().to_map()),
+        "fed\ncba\n"
+    );
+    assert_eq!(
+        to_string(map.rotated_clockwise().flip_horizontal().to_map()),
+        "ad\nbe\ncf\n"
+    );
+}
+
+#[test]
+fn test_reflections() {
+    let map: Map<char> = Map::from("#..#\n.##.\n.##.\n#..#\n#.##");
+    let reflections = map.reflections(0);
+    assert_eq!(
+        reflections,
+        vec![Reflection {
+            axis: Axis::Horizontal,
+            offset: 2,
+            differences: vec![],
+        }]
+    );
+
+    let smudged = map.reflections(1);
+    assert!(smudged.contains(&Reflection {
+        axis: Axis::Vertical,
+        offset: 2,
+        differences: vec![(Position { x: 1, y: 4 }, Position { x: 2, y: 4 })],
+    }));
+    assert_eq!(smudged[0].summary(), 200);
+}
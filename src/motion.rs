@@ -0,0 +1,257 @@
+use std::str::FromStr;
+
+use num::{rational::Ratio, BigInt, BigRational, Integer, Zero};
+
+/// Position and constant velocity of an object, in three dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trajectory {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
+
+fn parse_vector(s: &str) -> Result<[i64; 3], String> {
+    let values = s
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number in {s}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    values
+        .try_into()
+        .map_err(|_| format!("expected 3 coordinates, got {s}"))
+}
+
+impl FromStr for Trajectory {
+    type Err = String;
+    /// Parses `19, 13, 30 @ -2,  1, -2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s
+            .split_once('@')
+            .ok_or_else(|| format!("missing velocity in {s}"))?;
+        Ok(Self {
+            position: parse_vector(position)?,
+            velocity: parse_vector(velocity)?,
+        })
+    }
+}
+
+/// Inclusive square `[min, max]²` on the xy-plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub min: i128,
+    pub max: i128,
+}
+
+impl Window {
+    pub fn contains(&self, (x, y): &(Ratio<i128>, Ratio<i128>)) -> bool {
+        let range = Ratio::from_integer(self.min)..=Ratio::from_integer(self.max);
+        range.contains(x) && range.contains(y)
+    }
+}
+
+impl Trajectory {
+    /// Crossing point of the two paths projected on the xy-plane, ignoring time:
+    /// both objects only need to pass through it at some point in their future.
+    /// Parallel paths never cross, even when they overlap.
+    pub fn intersect_xy(&self, other: &Trajectory) -> Option<(Ratio<i128>, Ratio<i128>)> {
+        let [px, py, _] = self.position.map(i128::from);
+        let [vx, vy, _] = self.velocity.map(i128::from);
+        let [qx, qy, _] = other.position.map(i128::from);
+        let [wx, wy, _] = other.velocity.map(i128::from);
+
+        // p + t·v = q + s·w, solved with Cramer's rule
+        let determinant = vx * -wy + wx * vy;
+        if determinant == 0 {
+            return None;
+        }
+        let (dx, dy) = (qx - px, qy - py);
+        let t = Ratio::new(dx * -wy + wx * dy, determinant);
+        let s = Ratio::new(vx * dy - dx * vy, determinant);
+        if t < Ratio::zero() || s < Ratio::zero() {
+            return None;
+        }
+        Some((
+            Ratio::from_integer(px) + t * vx,
+            Ratio::from_integer(py) + t * vy,
+        ))
+    }
+
+    fn wide(&self) -> ([i128; 3], [i128; 3]) {
+        (self.position.map(i128::from), self.velocity.map(i128::from))
+    }
+
+    /// Whether both objects are at the same place at the same, non-negative, time.
+    pub fn collides_with(&self, other: &Trajectory) -> bool {
+        let (p, v) = self.wide();
+        let (q, w) = other.wide();
+        let (dp, dv) = (sub(q, p), sub(v, w));
+        if cross(dp, dv) != [0; 3] {
+            return false;
+        }
+        // dp = t·dv, dv being null only if both stay together or never meet
+        match (0..3).find(|&i| dv[i] != 0) {
+            Some(i) => dp[i] % dv[i] == 0 && dp[i] / dv[i] >= 0,
+            None => dp == [0; 3],
+        }
+    }
+}
+
+/// Number of pairs whose future xy paths cross inside `window`.
+pub fn count_intersections_xy(trajectories: &[Trajectory], window: &Window) -> usize {
+    trajectories
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| trajectories[i + 1..].iter().map(move |b| (a, b)))
+        .filter_map(|(a, b)| a.intersect_xy(b))
+        .filter(|point| window.contains(point))
+        .count()
+}
+
+/// Solves `matrix · x = rhs` by Gaussian elimination, without any rounding.
+/// Returns `None` when the system has no unique solution.
+pub fn solve_linear<T>(
+    mut matrix: Vec<Vec<Ratio<T>>>,
+    mut rhs: Vec<Ratio<T>>,
+) -> Option<Vec<Ratio<T>>>
+where
+    T: Clone + Integer,
+{
+    let n = rhs.len();
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| !matrix[row][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        for row in 0..n {
+            if row == column || matrix[row][column].is_zero() {
+                continue;
+            }
+            let factor = matrix[row][column].clone() / matrix[column][column].clone();
+            let pivot_row = matrix[column].clone();
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                *value = value.clone() - factor.clone() * pivot_value;
+            }
+            let delta = factor * rhs[column].clone();
+            rhs[row] = rhs[row].clone() - delta;
+        }
+    }
+    Some(
+        rhs.into_iter()
+            .zip(matrix)
+            .enumerate()
+            .map(|(i, (value, row))| value / row[i].clone())
+            .collect(),
+    )
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+// P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i, linear in P and V
+fn pair_equations(a: &Trajectory, b: &Trajectory) -> [([i128; 6], i128); 3] {
+    let (pa, va) = a.wide();
+    let (pb, vb) = b.wide();
+    let [ax, ay, az] = sub(vb, va);
+    let [bx, by, bz] = sub(pb, pa);
+    let [cx, cy, cz] = sub(cross(pb, vb), cross(pa, va));
+    [
+        ([0, az, -ay, 0, -bz, by], cx),
+        ([-az, 0, ax, bz, 0, -bx], cy),
+        ([ay, -ax, 0, -by, bx, 0], cz),
+    ]
+}
+
+/// The integer trajectory colliding with every given one, each at its own time.
+pub fn intercepting_trajectory(trajectories: &[Trajectory]) -> Option<Trajectory> {
+    let first = trajectories.first()?;
+    let solution = trajectories[1..]
+        .iter()
+        .enumerate()
+        .flat_map(|(i, b)| trajectories[i + 2..].iter().map(move |c| (b, c)))
+        .find_map(|(b, c)| {
+            let (matrix, rhs): (Vec<_>, Vec<_>) = pair_equations(first, b)
+                .into_iter()
+                .chain(pair_equations(first, c))
+                .map(|(row, value)| {
+                    let to_rational = |v: i128| BigRational::from_integer(BigInt::from(v));
+                    (row.map(to_rational).to_vec(), to_rational(value))
+                })
+                .unzip();
+            solve_linear(matrix, rhs)
+        })?;
+
+    let values = solution
+        .iter()
+        .map(|value| {
+            value
+                .is_integer()
+                .then(|| i64::try_from(value.to_integer()).ok())
+                .flatten()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let rock = Trajectory {
+        position: [values[0], values[1], values[2]],
+        velocity: [values[3], values[4], values[5]],
+    };
+    trajectories
+        .iter()
+        .all(|trajectory| rock.collides_with(trajectory))
+        .then_some(rock)
+}
+
+#[cfg(test)]
+fn example() -> Vec<Trajectory> {
+    [
+        "19, 13, 30 @ -2,  1, -2",
+        "18, 19, 22 @ -1, -1, -2",
+        "20, 25, 34 @ -2, -2, -4",
+        "12, 31, 28 @ -1, -2, -1",
+        "20, 19, 15 @  1, -5, -3",
+    ]
+    .iter()
+    .map(|line| line.parse().unwrap())
+    .collect()
+}
+
+#[test]
+fn test_intersect_xy() {
+    let stones = example();
+    let (x, y) = stones[0].intersect_xy(&stones[1]).unwrap();
+    assert_eq!((x, y), (Ratio::new(43, 3), Ratio::new(46, 3)));
+    // parallel
+    assert_eq!(stones[1].intersect_xy(&stones[2]), None);
+    // crossed in the past for the first one
+    assert_eq!(stones[0].intersect_xy(&stones[4]), None);
+    assert_eq!(
+        count_intersections_xy(&stones, &Window { min: 7, max: 27 }),
+        2
+    );
+}
+
+#[test]
+fn test_solve_linear() {
+    // x + y = 3, x - y = 1
+    let r = |v: i64| Ratio::from_integer(v);
+    let solution = solve_linear(vec![vec![r(1), r(1)], vec![r(1), r(-1)]], vec![r(3), r(1)]);
+    assert_eq!(solution, Some(vec![r(2), r(1)]));
+    let singular = solve_linear(vec![vec![r(1), r(1)], vec![r(2), r(2)]], vec![r(3), r(6)]);
+    assert_eq!(singular, None);
+}
+
+#[test]
+fn test_intercepting_trajectory() {
+    let rock = intercepting_trajectory(&example()).unwrap();
+    assert_eq!(rock.position, [24, 13, 10]);
+    assert_eq!(rock.velocity, [-3, 1, 2]);
+}
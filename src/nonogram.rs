@@ -0,0 +1,340 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Filled,
+    Empty,
+    Unknown,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = String;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Cell::Filled),
+            '.' => Ok(Cell::Empty),
+            '?' => Ok(Cell::Unknown),
+            _ => Err(format!("unknown cell {c}")),
+        }
+    }
+}
+
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Filled => '#',
+            Cell::Empty => '.',
+            Cell::Unknown => '?',
+        }
+    }
+}
+
+pub fn to_string(cells: &[Cell]) -> String {
+    cells.iter().map(|&cell| char::from(cell)).collect()
+}
+
+/// A row of cells along with the lengths of its consecutive filled blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub cells: Vec<Cell>,
+    pub clues: Vec<usize>,
+}
+
+impl FromStr for Line {
+    type Err = String;
+    /// Parses `???.### 1,1,3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (cells, clues) = s
+            .split_once(' ')
+            .ok_or_else(|| format!("missing clues in {s}"))?;
+        Ok(Line {
+            cells: cells
+                .chars()
+                .map(Cell::try_from)
+                .collect::<Result<_, _>>()?,
+            clues: clues
+                .split(',')
+                .map(|clue| clue.parse().map_err(|_| format!("invalid clue {clue}")))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Line {
+    /// Repeats the cells `factor` times separated by an unknown cell, and the clues as well.
+    pub fn unfold(&self, factor: usize) -> Line {
+        let mut cells = vec![];
+        for i in 0..factor {
+            if i > 0 {
+                cells.push(Cell::Unknown);
+            }
+            cells.extend(&self.cells);
+        }
+        Line {
+            cells,
+            clues: self.clues.repeat(factor),
+        }
+    }
+
+    fn can_be(&self, index: usize, cell: Cell) -> bool {
+        self.cells[index] == cell || self.cells[index] == Cell::Unknown
+    }
+
+    fn block_fits(&self, start: usize, len: usize) -> bool {
+        start + len <= self.cells.len()
+            && (start..start + len).all(|i| self.can_be(i, Cell::Filled))
+    }
+
+    pub fn solve(&self) -> Solutions<'_> {
+        let (n, m) = (self.cells.len(), self.clues.len());
+
+        // suffix[i][j]: fillings of cells[i..] with clues[j..]
+        let mut suffix = vec![vec![0; m + 1]; n + 1];
+        suffix[n][m] = 1;
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let mut count = 0;
+                if self.can_be(i, Cell::Empty) {
+                    count += suffix[i + 1][j];
+                }
+                if j < m && self.block_fits(i, self.clues[j]) {
+                    let end = i + self.clues[j];
+                    if end == n {
+                        count += suffix[n][j + 1];
+                    } else if self.can_be(end, Cell::Empty) {
+                        count += suffix[end + 1][j + 1];
+                    }
+                }
+                suffix[i][j] = count;
+            }
+        }
+
+        // prefix[i][j]: fillings of cells[..i] with clues[..j]
+        let mut prefix = vec![vec![0; m + 1]; n + 1];
+        prefix[0][0] = 1;
+        for i in 1..=n {
+            for j in 0..=m {
+                let mut count = 0;
+                if self.can_be(i - 1, Cell::Empty) {
+                    count += prefix[i - 1][j];
+                }
+                if j > 0
+                    && i >= self.clues[j - 1]
+                    && self.block_fits(i - self.clues[j - 1], self.clues[j - 1])
+                {
+                    let start = i - self.clues[j - 1];
+                    if start == 0 {
+                        count += prefix[0][j - 1];
+                    } else if self.can_be(start - 1, Cell::Empty) {
+                        count += prefix[start - 1][j - 1];
+                    }
+                }
+                prefix[i][j] = count;
+            }
+        }
+
+        Solutions {
+            line: self,
+            suffix,
+            prefix,
+        }
+    }
+}
+
+/// Counting tables over the valid fillings of a line.
+#[derive(Debug, Clone)]
+pub struct Solutions<'a> {
+    line: &'a Line,
+    suffix: Vec<Vec<u64>>,
+    prefix: Vec<Vec<u64>>,
+}
+
+impl Solutions<'_> {
+    pub fn count(&self) -> u64 {
+        self.suffix[0][0]
+    }
+
+    /// The `index`-th filling, empty cells being tried before blocks.
+    pub fn nth(&self, mut index: u64) -> Option<Vec<Cell>> {
+        if index >= self.count() {
+            return None;
+        }
+        let line = self.line;
+        let n = line.cells.len();
+        let mut cells = Vec::with_capacity(n);
+        let (mut i, mut j) = (0, 0);
+        while i < n {
+            if line.can_be(i, Cell::Empty) {
+                let empty = self.suffix[i + 1][j];
+                if index < empty {
+                    cells.push(Cell::Empty);
+                    i += 1;
+                    continue;
+                }
+                index -= empty;
+            }
+            let end = i + line.clues[j];
+            cells.extend(std::iter::repeat_n(Cell::Filled, line.clues[j]));
+            if end < n {
+                cells.push(Cell::Empty);
+            }
+            (i, j) = (end + 1, j + 1);
+        }
+        Some(cells)
+    }
+
+    pub fn fillings(&self) -> impl Iterator<Item = Vec<Cell>> + '_ {
+        (0..self.count()).filter_map(|index| self.nth(index))
+    }
+
+    /// Picks a filling from a random number, uniformly up to the modulo bias.
+    pub fn sample(&self, random: u64) -> Option<Vec<Cell>> {
+        self.nth(random.checked_rem(self.count())?)
+    }
+
+    /// The cells taking the same value in every filling, the others being unknown.
+    /// `None` when the line cannot be filled at all.
+    pub fn forced(&self) -> Option<Vec<Cell>> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+        let line = self.line;
+        let n = line.cells.len();
+        // filled[i]: number of fillings where cell i is filled, accumulated as differences
+        let mut differences = vec![0i128; n + 1];
+        for (j, &len) in line.clues.iter().enumerate() {
+            for start in 0..n {
+                if !line.block_fits(start, len) {
+                    continue;
+                }
+                let before = match start {
+                    0 => self.prefix[0][j],
+                    _ if line.can_be(start - 1, Cell::Empty) => self.prefix[start - 1][j],
+                    _ => 0,
+                };
+                let end = start + len;
+                let after = if end == n {
+                    self.suffix[n][j + 1]
+                } else if line.can_be(end, Cell::Empty) {
+                    self.suffix[end + 1][j + 1]
+                } else {
+                    0
+                };
+                let count = before as i128 * after as i128;
+                differences[start] += count;
+                differences[end] -= count;
+            }
+        }
+        let mut filled = 0;
+        Some(
+            differences[..n]
+                .iter()
+                .map(|difference| {
+                    filled += difference;
+                    match filled {
+                        0 => Cell::Empty,
+                        f if f == total as i128 => Cell::Filled,
+                        _ => Cell::Unknown,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Fills a grid from its row and column clues by propagating forced cells line by line
+/// until nothing changes. Cells still unknown at that point are left as such.
+pub fn solve_grid(rows: &[Vec<usize>], columns: &[Vec<usize>]) -> Option<Vec<Vec<Cell>>> {
+    let mut grid = vec![vec![Cell::Unknown; columns.len()]; rows.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (y, clues) in rows.iter().enumerate() {
+            let line = Line {
+                cells: grid[y].clone(),
+                clues: clues.clone(),
+            };
+            let forced = line.solve().forced()?;
+            changed |= forced != grid[y];
+            grid[y] = forced;
+        }
+        for (x, clues) in columns.iter().enumerate() {
+            let line = Line {
+                cells: grid.iter().map(|row| row[x]).collect(),
+                clues: clues.clone(),
+            };
+            for (y, cell) in line.solve().forced()?.into_iter().enumerate() {
+                changed |= grid[y][x] != cell;
+                grid[y][x] = cell;
+            }
+        }
+    }
+    Some(grid)
+}
+
+#[test]
+fn test_count() {
+    let count = |s: &str| Line::from_str(s).unwrap().solve().count();
+    assert_eq!(count("???.### 1,1,3"), 1);
+    assert_eq!(count(".??..??...?##. 1,1,3"), 4);
+    assert_eq!(count("?###???????? 3,2,1"), 10);
+    assert_eq!(count("### 1"), 0);
+}
+
+#[test]
+fn test_unfold() {
+    let line = Line::from_str(".# 1").unwrap().unfold(3);
+    assert_eq!(to_string(&line.cells), ".#?.#?.#");
+    assert_eq!(line.clues, vec![1, 1, 1]);
+    let line = Line::from_str("?###???????? 3,2,1").unwrap().unfold(5);
+    assert_eq!(line.solve().count(), 506250);
+}
+
+#[test]
+fn test_fillings() {
+    let line = Line::from_str("???.### 1,1,3").unwrap();
+    let solutions = line.solve();
+    let fillings = solutions
+        .fillings()
+        .map(|f| to_string(&f))
+        .collect::<Vec<_>>();
+    assert_eq!(fillings, vec!["#.#.###"]);
+
+    let line = Line::from_str("???? 1,1").unwrap();
+    let solutions = line.solve();
+    let fillings = solutions
+        .fillings()
+        .map(|f| to_string(&f))
+        .collect::<Vec<_>>();
+    assert_eq!(fillings, vec![".#.#", "#..#", "#.#."]);
+    assert_eq!(
+        solutions.sample(4).map(|f| to_string(&f)),
+        Some("#..#".to_owned())
+    );
+    assert_eq!(solutions.nth(3), None);
+}
+
+#[test]
+fn test_forced() {
+    let forced = |s: &str| {
+        Line::from_str(s)
+            .unwrap()
+            .solve()
+            .forced()
+            .map(|f| to_string(&f))
+    };
+    assert_eq!(forced("?????????? 8").as_deref(), Some("??######??"));
+    assert_eq!(forced("???.### 1,1,3").as_deref(), Some("#.#.###"));
+    assert_eq!(forced("?#?? 3").as_deref(), Some("?##?"));
+    assert_eq!(forced("### 1"), None);
+}
+
+#[test]
+fn test_solve_grid() {
+    // a plus sign
+    let clues = vec![vec![1], vec![3], vec![1]];
+    let grid = solve_grid(&clues, &clues).unwrap();
+    let rows = grid.iter().map(|row| to_string(row)).collect::<Vec<_>>();
+    assert_eq!(rows, vec![".#.", "###", ".#."]);
+}
@@ -0,0 +1,478 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Position in the text being parsed. Parsers only ever move it forwards, and keep the whole
+/// text around so errors can tell where they happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Cursor { text, offset: 0 }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    fn advance(self, bytes: usize) -> Self {
+        Cursor {
+            offset: self.offset + bytes,
+            ..self
+        }
+    }
+
+    /// Line and column of the cursor, both starting at 1.
+    pub fn location(&self) -> (usize, usize) {
+        let before = &self.text[..self.offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let (line, column) = self.location();
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> Self {
+        error.to_string()
+    }
+}
+
+pub type ParseResult<'a, T> = Result<(T, Cursor<'a>), ParseError>;
+
+/// Anything turning the text at a cursor into a value, returning the cursor past it.
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Cursor<'a>) -> ParseResult<'a, T>;
+
+    /// Borrows the parser, to use it in several places.
+    fn by_ref(&self) -> impl Parser<'a, T> + '_
+    where
+        Self: Sized,
+    {
+        move |input: Cursor<'a>| self.parse(input)
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Cursor<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Cursor<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs `parser` on the whole `text`, only allowing trailing newlines after it.
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, text: &'a str) -> Result<T, ParseError> {
+    let (value, rest) = parser.parse(Cursor::new(text))?;
+    let (_, rest) = skip_line_breaks(rest);
+    if !rest.is_at_end() {
+        return Err(rest.error("end of input"));
+    }
+    Ok(value)
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Cursor<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok((
+                &input.rest()[..expected.len()],
+                input.advance(expected.len()),
+            ))
+        } else {
+            Err(input.error(format!("{expected:?}")))
+        }
+    }
+}
+
+/// A single character among `chars`.
+pub fn one_of<'a>(chars: &'static str) -> impl Parser<'a, char> {
+    move |input: Cursor<'a>| match input.rest().chars().next() {
+        Some(c) if chars.contains(c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(input.error(format!("one of {chars:?}"))),
+    }
+}
+
+/// The longest, possibly empty, run of characters matching `predicate`.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: Cursor<'a>| {
+        let rest = input.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        Ok((&rest[..length], input.advance(length)))
+    }
+}
+
+/// Skips any number of spaces, newlines excluded.
+pub fn spaces(input: Cursor<'_>) -> ParseResult<'_, ()> {
+    let (_, input) = take_while(|c| c == ' ').parse(input)?;
+    Ok(((), input))
+}
+
+/// A non-empty run of letters and digits.
+pub fn word(input: Cursor<'_>) -> ParseResult<'_, &str> {
+    match take_while(|c| c.is_ascii_alphanumeric()).parse(input)? {
+        ("", _) => Err(input.error("a word")),
+        result => Ok(result),
+    }
+}
+
+/// Digits with an optional leading minus sign.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Cursor<'a>| {
+        let sign = usize::from(input.rest().starts_with('-'));
+        let (digits, _) = take_while(|c| c.is_ascii_digit()).parse(input.advance(sign))?;
+        let length = sign + digits.len();
+        match input.rest()[..length].parse() {
+            Ok(value) if !digits.is_empty() => Ok((value, input.advance(length))),
+            _ => Err(input.error("an integer")),
+        }
+    }
+}
+
+/// At least one integer, separated and possibly preceded by spaces.
+pub fn integers<'a, T: FromStr>() -> impl Parser<'a, Vec<T>> {
+    move |input: Cursor<'a>| {
+        let (first, mut input) = preceded(spaces, integer()).parse(input)?;
+        let mut values = vec![first];
+        while let Ok((value, rest)) = preceded(spaces, integer()).parse(input) {
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Cursor<'a>| {
+        let (value, rest) = parser.parse(input)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Like `map`, a failing conversion being reported where the value started.
+pub fn try_map<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> Result<U, String>,
+) -> impl Parser<'a, U> {
+    move |input: Cursor<'a>| {
+        let (value, rest) = parser.parse(input)?;
+        Ok((f(value).map_err(|expected| input.error(expected))?, rest))
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: Cursor<'a>| {
+        let (a, input) = first.parse(input)?;
+        let (b, input) = second.parse(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Tries `second` from the same place when `first` fails, reporting the error that went
+/// the furthest when both do.
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Cursor<'a>| {
+        first.parse(input).or_else(|error| {
+            second.parse(input).map_err(|other| {
+                if (other.line, other.column) >= (error.line, error.column) {
+                    other
+                } else {
+                    error
+                }
+            })
+        })
+    }
+}
+
+/// As many values as `parser` accepts in a row, possibly none. Stops at the first value
+/// taking up no input, which would otherwise repeat forever.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Cursor<'a>| {
+        let mut values = vec![];
+        while let Ok((value, rest)) = parser.parse(input) {
+            if rest.offset == input.offset {
+                break;
+            }
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// At least one value, separated by `separator`. A separator must be followed by a value.
+pub fn separated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Cursor<'a>| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+        while let Ok((_, rest)) = separator.parse(input) {
+            let (value, rest) = parser.parse(rest)?;
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// `key`, `separator` then `value`, as in `x=787`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(terminated(key, tag(separator)), value)
+}
+
+/// A record such as `Game 12: ...`, returning its number along with its `body`.
+pub fn labelled<'a, I: FromStr, T>(
+    label: &'static str,
+    body: impl Parser<'a, T>,
+) -> impl Parser<'a, (I, T)> {
+    pair(
+        delimited(pair(tag(label), spaces), integer(), pair(tag(":"), spaces)),
+        body,
+    )
+}
+
+// length of the `\n` or `\r\n` opening `text`, 0 if there is none
+fn line_break(text: &str) -> usize {
+    if text.starts_with("\r\n") {
+        2
+    } else {
+        usize::from(text.starts_with('\n'))
+    }
+}
+
+// skips consecutive line breaks, returning how many there were
+fn skip_line_breaks(mut input: Cursor<'_>) -> (usize, Cursor<'_>) {
+    let mut count = 0;
+    while let length @ 1.. = line_break(input.rest()) {
+        input = input.advance(length);
+        count += 1;
+    }
+    (count, input)
+}
+
+/// End of the current line, `\n` or `\r\n`, or of the text.
+pub fn line_end(input: Cursor<'_>) -> ParseResult<'_, ()> {
+    match line_break(input.rest()) {
+        0 if !input.is_at_end() => Err(input.error("end of line")),
+        length => Ok(((), input.advance(length))),
+    }
+}
+
+/// One value per line, each line being entirely consumed by `parser`. Stops at a blank line.
+pub fn lines<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Cursor<'a>| {
+        let mut values = vec![];
+        while !input.is_at_end() && line_break(input.rest()) == 0 {
+            let (value, rest) = parser.parse(input)?;
+            let (_, rest) = line_end(rest)?;
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Values separated by blank lines, each block being entirely consumed by `parser`.
+pub fn blocks<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Cursor<'a>| {
+        let mut values = vec![];
+        loop {
+            let (value, rest) = parser.parse(input)?;
+            values.push(value);
+            let (newlines, after) = skip_line_breaks(rest);
+            input = after;
+            if input.is_at_end() {
+                return Ok((values, input));
+            }
+            // a block may or may not have consumed its own line end
+            let line_ended = rest.text[..rest.offset].ends_with('\n');
+            if newlines + usize::from(line_ended) < 2 {
+                return Err(rest.error("blank line"));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_location() {
+    let cursor = Cursor::new("ab\ncd\nef").advance(4);
+    assert_eq!(cursor.location(), (2, 2));
+    assert_eq!(cursor.rest(), "d\nef");
+    assert_eq!(
+        cursor.error("x").to_string(),
+        "line 2, column 2: expected x"
+    );
+}
+
+#[test]
+fn test_integers() {
+    assert_eq!(parse_all(integer::<i32>(), "-42"), Ok(-42));
+    assert_eq!(
+        parse_all(integers::<u32>(), " 41 48  83"),
+        Ok(vec![41, 48, 83])
+    );
+    assert_eq!(
+        parse_all(integer::<u8>(), "300").unwrap_err().expected,
+        "an integer"
+    );
+    assert!(parse_all(integer::<i32>(), "-").is_err());
+}
+
+#[test]
+fn test_labelled_record() {
+    let throw = separated(
+        pair(terminated(integer::<u32>(), tag(" ")), word),
+        tag(", "),
+    );
+    let game = labelled::<u32, _>("Game", separated(throw, tag("; ")));
+    assert_eq!(
+        parse_all(game.by_ref(), "Game 3: 1 red, 2 blue; 4 green"),
+        Ok((3, vec![vec![(1, "red"), (2, "blue")], vec![(4, "green")]]))
+    );
+    let error = parse_all(
+        lines(game.by_ref()),
+        "Game 1: 1 red\nGame 2: 3 blue, two green",
+    )
+    .unwrap_err();
+    assert_eq!((error.line, error.column), (2, 17));
+    assert_eq!(error.expected, "an integer");
+}
+
+#[test]
+fn test_key_values() {
+    let part = delimited(
+        tag("{"),
+        separated(key_value(one_of("xmas"), "=", integer::<u32>()), tag(",")),
+        tag("}"),
+    );
+    assert_eq!(
+        parse_all(part, "{x=787,m=2655}"),
+        Ok(vec![('x', 787), ('m', 2655)])
+    );
+}
+
+#[test]
+fn test_alt_and_many() {
+    let rule = terminated(terminated(word, tag(":")), tag(","));
+    let parser = pair(
+        many(rule),
+        alt(map(tag("!"), str::to_owned), map(word, str::to_owned)),
+    );
+    assert_eq!(
+        parse_all(parser.by_ref(), "a:,b:,c"),
+        Ok((vec!["a", "b"], "c".to_owned()))
+    );
+    assert_eq!(
+        parse_all(parser.by_ref(), "!"),
+        Ok((vec![], "!".to_owned()))
+    );
+    let error = parse_all(parser.by_ref(), "a:,?").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 4));
+}
+
+#[test]
+fn test_many_stops_without_progress() {
+    assert_eq!(
+        parse_all(pair(many(spaces), word), "  ab"),
+        Ok((vec![()], "ab"))
+    );
+    assert_eq!(parse_all(many(take_while(|c| c == 'x')), ""), Ok(vec![]));
+}
+
+#[test]
+fn test_blocks() {
+    let block = preceded(
+        pair(take_while(|c| c != ':'), tag(":\n")),
+        lines(integers::<u64>()),
+    );
+    let text = "a:\n1 2\n3\n\nb:\n4\n";
+    assert_eq!(
+        parse_all(blocks(block.by_ref()), text),
+        Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+    );
+    let error = parse_all(blocks(block.by_ref()), "a:\n1\nb:\n2").unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.expected.as_str()),
+        (3, 1, "an integer")
+    );
+}
+
+#[test]
+fn test_crlf_line_ends() {
+    let block = preceded(
+        pair(take_while(|c| c != ':'), tag(":\r\n")),
+        lines(integers::<u64>()),
+    );
+    let text = "a:\r\n1 2\r\n3\r\n\r\nb:\r\n4\r\n";
+    assert_eq!(
+        parse_all(blocks(block.by_ref()), text),
+        Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+    );
+    let error = parse_all(lines(integer::<u32>()), "1\r\n2\r3\r\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.expected.as_str()),
+        (2, 2, "end of line")
+    );
+}
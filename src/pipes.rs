@@ -0,0 +1,232 @@
+use crate::{
+    direction::Direction,
+    map::{Map, Position},
+};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pipe {
+    Vertical,
+    Horizontal,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start,
+}
+
+impl From<char> for Pipe {
+    fn from(c: char) -> Self {
+        match c {
+            '|' => Self::Vertical,
+            '-' => Self::Horizontal,
+            'L' => Self::NorthEast,
+            'J' => Self::NorthWest,
+            '7' => Self::SouthWest,
+            'F' => Self::SouthEast,
+            '.' => Self::Ground,
+            'S' => Self::Start,
+            _ => unreachable!("Cannot parse {} into Pipe", c),
+        }
+    }
+}
+
+impl Pipe {
+    /// Directions the pipe leads to, none for the ground and the unknown start.
+    pub fn connections(&self) -> &'static [Direction] {
+        match self {
+            Pipe::Vertical => &[Direction::Up, Direction::Down],
+            Pipe::Horizontal => &[Direction::Left, Direction::Right],
+            Pipe::NorthEast => &[Direction::Up, Direction::Right],
+            Pipe::NorthWest => &[Direction::Up, Direction::Left],
+            Pipe::SouthWest => &[Direction::Down, Direction::Left],
+            Pipe::SouthEast => &[Direction::Down, Direction::Right],
+            Pipe::Ground | Pipe::Start => &[],
+        }
+    }
+
+    pub fn connects(&self, direction: Direction) -> bool {
+        self.connections().contains(&direction)
+    }
+
+    /// The pipe joining both directions, in any order.
+    pub fn joining(a: Direction, b: Direction) -> Option<Pipe> {
+        [
+            Pipe::Vertical,
+            Pipe::Horizontal,
+            Pipe::NorthEast,
+            Pipe::NorthWest,
+            Pipe::SouthWest,
+            Pipe::SouthEast,
+        ]
+        .into_iter()
+        .find(|pipe| a != b && pipe.connects(a) && pipe.connects(b))
+    }
+
+    /// Direction to leave by when entering the pipe going `direction`.
+    pub fn exit(&self, direction: Direction) -> Option<Direction> {
+        let entry = direction.opposite();
+        match *self.connections() {
+            [a, b] if a == entry => Some(b),
+            [a, b] if b == entry => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// A field of pipes holding a single loop through the start.
+#[derive(Debug, Clone)]
+pub struct Sketch {
+    pub map: Map<Pipe>,
+    pub start: Position,
+}
+
+impl TryFrom<&str> for Sketch {
+    type Error = String;
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let map: Map<Pipe> = Map::from(input);
+        let start = map
+            .get_symbol_and_position(|pipe| pipe == Pipe::Start)
+            .first()
+            .map(|(_, position)| *position)
+            .ok_or("missing start")?;
+        let mut sketch = Sketch { map, start };
+        sketch.place_start_pipe()?;
+        Ok(sketch)
+    }
+}
+
+impl Sketch {
+    fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.to_tuple();
+        let position = Position {
+            x: position.x.checked_add_signed(dx)?,
+            y: position.y.checked_add_signed(dy)?,
+        };
+        (!self.map.is_out_of_bounds(position)).then_some(position)
+    }
+
+    /// Puts under the start the pipe whose loop closes back on it, trying every pair of
+    /// neighbours leading into the start.
+    fn place_start_pipe(&mut self) -> Result<(), String> {
+        let connected = DIRECTIONS
+            .into_iter()
+            .filter(|&direction| {
+                self.step(self.start, direction)
+                    .is_some_and(|next| self.map.get(next).connects(direction.opposite()))
+            })
+            .collect::<Vec<_>>();
+        let candidates = connected.iter().enumerate().flat_map(|(i, &a)| {
+            connected[i + 1..]
+                .iter()
+                .filter_map(move |&b| Pipe::joining(a, b))
+        });
+        for pipe in candidates {
+            self.map.map[self.start.y][self.start.x] = pipe;
+            if self.trace().is_ok() {
+                return Ok(());
+            }
+        }
+        Err(format!(
+            "no loop through the start among its {} connections",
+            connected.len()
+        ))
+    }
+
+    /// Tiles of the loop in order, from the start back to the tile before it.
+    pub fn trace(&self) -> Result<Vec<Position>, String> {
+        let mut direction = self.map.get(self.start).connections()[0];
+        let mut position = self.start;
+        let mut polygon = vec![];
+        loop {
+            polygon.push(position);
+            position = self
+                .step(position, direction)
+                .ok_or_else(|| format!("loop leaves the map at {:?}", position))?;
+            direction = self
+                .map
+                .get(position)
+                .exit(direction)
+                .ok_or_else(|| format!("loop broken at {:?}", position))?;
+            if position == self.start {
+                return Ok(polygon);
+            }
+        }
+    }
+
+    /// Number of steps along the loop to its farthest tile from the start.
+    pub fn farthest(&self) -> Result<usize, String> {
+        Ok(self.trace()?.len() / 2)
+    }
+
+    /// Tiles enclosed by the loop. Scanning each row, the inside flips on every loop tile
+    /// leading north.
+    pub fn enclosed(&self) -> Result<usize, String> {
+        let mut on_loop = vec![vec![false; self.map.width()]; self.map.height()];
+        for position in self.trace()? {
+            on_loop[position.y][position.x] = true;
+        }
+        let mut enclosed = 0;
+        for (row, on_loop) in self.map.map.iter().zip(on_loop) {
+            let mut inside = false;
+            for (pipe, on_loop) in row.iter().zip(on_loop) {
+                if on_loop {
+                    inside ^= pipe.connects(Direction::Up);
+                } else if inside {
+                    enclosed += 1;
+                }
+            }
+        }
+        Ok(enclosed)
+    }
+}
+
+#[test]
+fn test_pipe_connections() {
+    assert_eq!(
+        Pipe::joining(Direction::Right, Direction::Up),
+        Some(Pipe::NorthEast)
+    );
+    assert_eq!(Pipe::joining(Direction::Up, Direction::Up), None);
+    assert_eq!(
+        Pipe::from('7').exit(Direction::Right),
+        Some(Direction::Down)
+    );
+    assert_eq!(Pipe::from('7').exit(Direction::Up), Some(Direction::Left));
+    assert_eq!(Pipe::from('7').exit(Direction::Down), None);
+}
+
+#[test]
+fn test_trace() {
+    let sketch = Sketch::try_from("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap();
+    assert_eq!(sketch.map.get(sketch.start), Pipe::SouthEast);
+    let polygon = sketch.trace().unwrap();
+    assert_eq!(polygon.len(), 8);
+    assert_eq!(polygon[1], Position::from((1, 2)));
+    assert_eq!(sketch.farthest(), Ok(4));
+    assert_eq!(sketch.enclosed(), Ok(1));
+    assert_eq!(crate::polygon::interior_points(&polygon), 1);
+}
+
+#[test]
+fn test_start_on_the_edge() {
+    let sketch = Sketch::try_from("S7\nLJ").unwrap();
+    assert_eq!(sketch.map.get(sketch.start), Pipe::SouthEast);
+    assert_eq!(sketch.enclosed(), Ok(0));
+    assert!(Sketch::try_from("S-\nLJ").is_err());
+    assert!(Sketch::try_from("S-\n.J").is_err());
+}
+
+#[test]
+fn test_start_with_extra_connection() {
+    let sketch = Sketch::try_from("F-7\n|.|\nS-J\n|..").unwrap();
+    assert_eq!(sketch.map.get(sketch.start), Pipe::NorthEast);
+    assert_eq!(sketch.trace().map(|polygon| polygon.len()), Ok(8));
+}
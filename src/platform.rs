@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+use crate::{cycle::state_after, direction::Direction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rock {
+    Rounded,
+    Cube,
+    Empty,
+}
+
+impl From<char> for Rock {
+    fn from(c: char) -> Self {
+        match c {
+            'O' => Self::Rounded,
+            '#' => Self::Cube,
+            '.' => Self::Empty,
+            _ => unreachable!("Cannot parse {} into Rock", c),
+        }
+    }
+}
+
+impl From<Rock> for char {
+    fn from(rock: Rock) -> Self {
+        match rock {
+            Rock::Rounded => 'O',
+            Rock::Cube => '#',
+            Rock::Empty => '.',
+        }
+    }
+}
+
+/// Rocks on a platform, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    rocks: Vec<Rock>,
+    width: usize,
+    height: usize,
+}
+
+impl From<&str> for Platform {
+    fn from(input: &str) -> Self {
+        let lines = input.lines().filter(|line| !line.is_empty());
+        let width = lines.clone().next().map_or(0, str::len);
+        let rocks = lines
+            .flat_map(|line| line.chars().map(Rock::from))
+            .collect::<Vec<_>>();
+        Platform {
+            height: rocks.len().checked_div(width).unwrap_or(0),
+            rocks,
+            width,
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(
+                f,
+                "{}",
+                row.iter().map(|&rock| char::from(rock)).collect::<String>()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Platform {
+    /// An empty platform has width 0 and no rocks, so there is no row to yield.
+    fn rows(&self) -> impl Iterator<Item = &[Rock]> {
+        self.rocks.chunks(self.width.max(1))
+    }
+
+    /// Rolls every rounded rock as far as it goes towards `direction`.
+    pub fn tilt(&mut self, direction: Direction) {
+        let (width, height) = (self.width as isize, self.height as isize);
+        // first cell of each line against the wall, the stride going away from it, the length
+        let (starts, stride, length): (Vec<isize>, isize, isize) = match direction {
+            Direction::Up => ((0..width).collect(), width, height),
+            Direction::Down => (
+                (0..width).map(|x| x + (height - 1) * width).collect(),
+                -width,
+                height,
+            ),
+            Direction::Left => ((0..height).map(|y| y * width).collect(), 1, width),
+            Direction::Right => (
+                (0..height).map(|y| y * width + width - 1).collect(),
+                -1,
+                width,
+            ),
+        };
+        for start in starts {
+            let mut free = start;
+            for step in 0..length {
+                let index = start + step * stride;
+                match self.rocks[index as usize] {
+                    Rock::Cube => free = index + stride,
+                    Rock::Rounded => {
+                        self.rocks.swap(free as usize, index as usize);
+                        free += stride;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    /// Tilts north, west, south then east.
+    pub fn spin_cycle(&mut self) {
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    /// Sum over rounded rocks of their distance to the south edge, counting their own row.
+    pub fn north_load(&self) -> usize {
+        self.rows()
+            .enumerate()
+            .map(|(y, row)| {
+                (self.height - y) * row.iter().filter(|&&rock| rock == Rock::Rounded).count()
+            })
+            .sum()
+    }
+
+    /// Platform after `cycles` spin cycles, skipping ahead once a state repeats.
+    pub fn after_spin_cycles(&self, cycles: usize) -> Platform {
+        state_after(
+            self.clone(),
+            |platform| {
+                let mut platform = platform.clone();
+                platform.spin_cycle();
+                platform
+            },
+            cycles,
+        )
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+#[test]
+fn test_tilt() {
+    let mut platform = Platform::from(EXAMPLE);
+    platform.tilt(Direction::Up);
+    assert_eq!(platform.north_load(), 136);
+    assert!(platform.to_string().starts_with("OOOO.#.O..\nOO..#....#\n"));
+
+    let mut platform = Platform::from("O.#.O\n");
+    platform.tilt(Direction::Right);
+    assert_eq!(platform.to_string(), ".O#.O\n");
+    platform.tilt(Direction::Left);
+    assert_eq!(platform.to_string(), "O.#O.\n");
+}
+
+#[test]
+fn test_empty_platform() {
+    for input in ["", "\n\n"] {
+        let mut platform = Platform::from(input);
+        platform.spin_cycle();
+        assert_eq!(platform.north_load(), 0);
+        assert_eq!(platform.to_string(), "");
+    }
+}
+
+#[test]
+fn test_spin_cycle() {
+    let mut platform = Platform::from(EXAMPLE);
+    platform.spin_cycle();
+    assert_eq!(
+        platform.to_string(),
+        ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+    );
+    assert_eq!(Platform::from(EXAMPLE).after_spin_cycles(1), platform);
+    assert_eq!(
+        Platform::from(EXAMPLE)
+            .after_spin_cycles(1_000_000_000)
+            .north_load(),
+        64
+    );
+}
@@ -0,0 +1,170 @@
+use num::integer::gcd;
+
+use crate::map::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl From<Position> for Point2 {
+    fn from(position: Position) -> Self {
+        Point2 {
+            x: position.x as i64,
+            y: position.y as i64,
+        }
+    }
+}
+
+fn edges<P>(polygon: &[P]) -> impl Iterator<Item = (Point2, Point2)> + '_
+where
+    P: Copy + Into<Point2>,
+{
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a.into(), b.into()))
+        .filter(|(a, b)| a != b)
+}
+
+/// Twice the signed area, positive when the vertices are counter-clockwise in a y-up frame.
+pub fn double_signed_area<P>(polygon: &[P]) -> i128
+where
+    P: Copy + Into<Point2>,
+{
+    edges(polygon)
+        .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+        .sum()
+}
+
+/// Shoelace area. The polygon may be given open or closed (first vertex repeated).
+pub fn area<P>(polygon: &[P]) -> u128
+where
+    P: Copy + Into<Point2>,
+{
+    double_signed_area(polygon).unsigned_abs() / 2
+}
+
+/// Number of lattice points on the boundary, which is its length for axis-aligned edges.
+pub fn boundary_length<P>(polygon: &[P]) -> u128
+where
+    P: Copy + Into<Point2>,
+{
+    edges(polygon)
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)) as u128)
+        .sum()
+}
+
+/// Lattice points strictly inside the polygon, from Pick's theorem: `A = i + b / 2 - 1`.
+/// Degenerate polygons, without any area, have none.
+pub fn interior_points<P>(polygon: &[P]) -> u128
+where
+    P: Copy + Into<Point2>,
+{
+    let double_area = double_signed_area(polygon).unsigned_abs();
+    if double_area == 0 {
+        return 0;
+    }
+    (double_area + 2).saturating_sub(boundary_length(polygon)) / 2
+}
+
+/// Lattice points inside or on the polygon.
+pub fn lattice_points<P>(polygon: &[P]) -> u128
+where
+    P: Copy + Into<Point2>,
+{
+    interior_points(polygon) + boundary_length(polygon)
+}
+
+pub fn is_on_boundary<P>(point: Point2, polygon: &[P]) -> bool
+where
+    P: Copy + Into<Point2>,
+{
+    edges(polygon).any(|(a, b)| {
+        cross(a, b, point) == 0
+            && a.x.min(b.x) <= point.x
+            && point.x <= a.x.max(b.x)
+            && a.y.min(b.y) <= point.y
+            && point.y <= a.y.max(b.y)
+    })
+}
+
+/// How many times the polygon winds around `point`; zero means outside.
+pub fn winding_number<P>(point: Point2, polygon: &[P]) -> i64
+where
+    P: Copy + Into<Point2>,
+{
+    edges(polygon)
+        .map(|(a, b)| {
+            if a.y <= point.y && b.y > point.y && cross(a, b, point) > 0 {
+                1
+            } else if a.y > point.y && b.y <= point.y && cross(a, b, point) < 0 {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Whether `point` is strictly inside the polygon, using the non-zero winding rule.
+pub fn contains<P>(point: Point2, polygon: &[P]) -> bool
+where
+    P: Copy + Into<Point2>,
+{
+    !is_on_boundary(point, polygon) && winding_number(point, polygon) != 0
+}
+
+fn cross(a: Point2, b: Point2, point: Point2) -> i128 {
+    (b.x - a.x) as i128 * (point.y - a.y) as i128 - (point.x - a.x) as i128 * (b.y - a.y) as i128
+}
+
+#[test]
+fn test_area_and_pick() {
+    let square = [(0, 0), (4, 0), (4, 3), (0, 3)].map(Point2::from);
+    assert_eq!(area(&square), 12);
+    assert_eq!(boundary_length(&square), 14);
+    assert_eq!(interior_points(&square), 6);
+    assert_eq!(lattice_points(&square), 20);
+
+    let closed = [(0, 0), (0, 3), (4, 3), (4, 0), (0, 0)].map(Point2::from);
+    assert_eq!(area(&closed), 12);
+    assert_eq!(interior_points(&closed), 6);
+
+    let triangle = [(0, 0), (4, 0), (0, 4)].map(Point2::from);
+    assert_eq!(boundary_length(&triangle), 12);
+    assert_eq!(interior_points(&triangle), 3);
+}
+
+#[test]
+fn test_degenerate_polygons() {
+    let segment = [(0, 0), (3, 0)].map(Point2::from);
+    assert_eq!(area(&segment), 0);
+    assert_eq!(interior_points(&segment), 0);
+    let flat = [(0, 0), (2, 2), (5, 5)].map(Point2::from);
+    assert_eq!(interior_points(&flat), 0);
+    assert_eq!(interior_points(&[Point2::default()]), 0);
+}
+
+#[test]
+fn test_winding_number() {
+    let square = [(-2, -2), (2, -2), (2, 2), (-2, 2)].map(Point2::from);
+    assert_eq!(winding_number(Point2 { x: 0, y: 0 }, &square), 1);
+    assert!(contains(Point2 { x: 1, y: -1 }, &square));
+    assert!(!contains(Point2 { x: 2, y: 0 }, &square));
+    assert!(is_on_boundary(Point2 { x: 2, y: 0 }, &square));
+    assert!(!contains(Point2 { x: 3, y: 0 }, &square));
+
+    let reversed = square.iter().rev().copied().collect::<Vec<_>>();
+    assert_eq!(winding_number(Point2 { x: 0, y: 0 }, &reversed), -1);
+
+    let positions = [(0, 0), (3, 0), (3, 3), (0, 3)].map(Position::from);
+    assert!(contains(Point2 { x: 1, y: 2 }, &positions));
+}
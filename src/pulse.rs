@@ -0,0 +1,508 @@
+use std::collections::{HashMap, VecDeque};
+
+use num::integer::lcm;
+
+use crate::graph::export::{GraphExport, Shape};
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Pulse {
+    High = 1,
+    Low = 0,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Communication {
+    pub source: String,
+    pub destination: String,
+    pub state: Pulse,
+}
+
+pub trait HandleCommunication {
+    fn handle_communication(&mut self, communication: &Communication) -> Vec<Communication>;
+
+    /// Called once per module sending to this one, after the whole network is parsed.
+    fn connect_input(&mut self, _source: &str) {}
+}
+
+/// Builds a module from its name and destinations. Registered per prefix character.
+pub type ModuleFactory = fn(String, Vec<String>) -> Box<dyn HandleCommunication>;
+
+#[derive(Debug, PartialEq, Eq)]
+enum State {
+    On,
+    Off,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FlipFlopModule {
+    id: String,
+    state: State,
+    destinations: Vec<String>,
+}
+
+impl FlipFlopModule {
+    pub fn new(id: String, destinations: Vec<String>) -> Self {
+        Self {
+            id,
+            state: State::Off,
+            destinations,
+        }
+    }
+}
+
+impl HandleCommunication for FlipFlopModule {
+    fn handle_communication(&mut self, communication: &Communication) -> Vec<Communication> {
+        match communication.state {
+            // If we get a high pulse, we don't do anything.
+            Pulse::High => {
+                vec![]
+            }
+            // If we get a low pulse, we flip our state and send a
+            // pulse to our destinations.
+            Pulse::Low => {
+                let (state, pulse) = match self.state {
+                    State::On => (State::Off, Pulse::Low),
+                    State::Off => (State::On, Pulse::High),
+                };
+                self.state = state;
+                send(&self.id, &self.destinations, pulse)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConjunctionModule {
+    id: String,
+    inputs: HashMap<String, Pulse>,
+    destinations: Vec<String>,
+}
+
+impl ConjunctionModule {
+    pub fn new(id: String, destinations: Vec<String>) -> Self {
+        ConjunctionModule {
+            id,
+            inputs: HashMap::new(),
+            destinations,
+        }
+    }
+}
+
+impl HandleCommunication for ConjunctionModule {
+    fn handle_communication(&mut self, communication: &Communication) -> Vec<Communication> {
+        // Update our inputs.
+        self.inputs
+            .insert(communication.source.clone(), communication.state);
+
+        // Figure out what pulse to send. It will be low if all the
+        // inputs are high.
+        let pulse_to_send = if self.inputs.values().all(|p| *p == Pulse::High) {
+            Pulse::Low
+        } else {
+            Pulse::High
+        };
+
+        send(&self.id, &self.destinations, pulse_to_send)
+    }
+
+    fn connect_input(&mut self, source: &str) {
+        // Default all inputs to low.
+        self.inputs.insert(source.to_owned(), Pulse::Low);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BroadcastModule {
+    id: String,
+    destinations: Vec<String>,
+}
+
+impl BroadcastModule {
+    pub fn new(name: String, destinations: Vec<String>) -> Self {
+        Self {
+            id: name,
+            destinations,
+        }
+    }
+}
+
+impl HandleCommunication for BroadcastModule {
+    fn handle_communication(&mut self, communication: &Communication) -> Vec<Communication> {
+        send(&self.id, &self.destinations, communication.state)
+    }
+}
+
+fn send(source: &str, destinations: &[String], state: Pulse) -> Vec<Communication> {
+    destinations
+        .iter()
+        .map(|d| Communication {
+            source: source.to_owned(),
+            destination: d.to_owned(),
+            state,
+        })
+        .collect()
+}
+
+pub const BROADCASTER: &str = "broadcaster";
+
+/// Module kinds of the day 20 puzzle: `%` flip-flops and `&` conjunctions.
+pub fn default_kinds() -> HashMap<char, ModuleFactory> {
+    let mut kinds: HashMap<char, ModuleFactory> = HashMap::new();
+    kinds.insert('%', |name, destinations| {
+        Box::new(FlipFlopModule::new(name, destinations))
+    });
+    kinds.insert('&', |name, destinations| {
+        Box::new(ConjunctionModule::new(name, destinations))
+    });
+    kinds
+}
+
+/// Watches for `module` sending `pulse`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Probe {
+    pub module: String,
+    pub pulse: Pulse,
+}
+
+/// Button presses at which a probe fires: first at `first`, then every `period` presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PulseCycle {
+    pub first: usize,
+    pub period: usize,
+}
+
+pub struct Network {
+    // Map of module name to module. The all implement
+    // HandleCommunication which allows us to collect them like this.
+    modules: HashMap<String, Box<dyn HandleCommunication>>,
+
+    // Prefix and destinations of every module, to describe the network
+    // without going through the trait objects.
+    kinds: HashMap<String, Option<char>>,
+    destinations: HashMap<String, Vec<String>>,
+
+    presses: usize,
+}
+
+impl Network {
+    pub fn parse(input: &str) -> Network {
+        Self::parse_with(input, &default_kinds())
+    }
+
+    /// Parses `name -> a, b` lines, building each module with the factory registered for
+    /// the first character of its name. The broadcaster has no prefix.
+    pub fn parse_with(input: &str, kinds: &HashMap<char, ModuleFactory>) -> Network {
+        let mut network = Network {
+            modules: HashMap::new(),
+            kinds: HashMap::new(),
+            destinations: HashMap::new(),
+            presses: 0,
+        };
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            // The first part is the name, the second part is the
+            // destinations, which are just comma separated.
+            let (declared, destinations) = line.split_once(" -> ").unwrap();
+            let destinations = destinations
+                .split(", ")
+                .map(|s| s.to_owned())
+                .collect::<Vec<_>>();
+
+            let kind = (declared != BROADCASTER).then(|| declared.chars().next().unwrap());
+            let name = module_name(declared, kind);
+            let module = match kind {
+                None => Box::new(BroadcastModule::new(name.clone(), destinations.clone())),
+                Some(prefix) => {
+                    let factory = kinds
+                        .get(&prefix)
+                        .unwrap_or_else(|| panic!("Unknown type {}", prefix));
+                    factory(name.clone(), destinations.clone())
+                }
+            };
+            network.kinds.insert(name.clone(), kind);
+            network.destinations.insert(name.clone(), destinations);
+            network.modules.insert(name, module);
+        }
+
+        for (source, destinations) in &network.destinations {
+            for destination in destinations {
+                if let Some(module) = network.modules.get_mut(destination) {
+                    module.connect_input(source);
+                }
+            }
+        }
+        network
+    }
+
+    pub fn destinations(&self) -> &HashMap<String, Vec<String>> {
+        &self.destinations
+    }
+
+    /// Prefix the module was declared with, `None` for the broadcaster.
+    pub fn kind(&self, module: &str) -> Option<char> {
+        self.kinds.get(module).copied().flatten()
+    }
+
+    /// Directed graph of the modules, shaped by kind and labelled with their prefix.
+    pub fn export(&self) -> GraphExport {
+        self.kinds.iter().fold(
+            GraphExport::directed(&self.destinations),
+            |export, (name, kind)| {
+                let (shape, label) = match kind {
+                    None => (Shape::Circle, name.clone()),
+                    Some('%') => (Shape::Box, format!("%{name}")),
+                    Some('&') => (Shape::Diamond, format!("&{name}")),
+                    Some(prefix) => (Shape::Round, format!("{prefix}{name}")),
+                };
+                export.with_shape(name, shape).with_label(name, &label)
+            },
+        )
+    }
+
+    pub fn inputs_of(&self, module: &str) -> Vec<&str> {
+        let mut inputs = self
+            .destinations
+            .iter()
+            .filter(|(_, destinations)| destinations.iter().any(|d| d == module))
+            .map(|(source, _)| source.as_str())
+            .collect::<Vec<_>>();
+        inputs.sort();
+        inputs
+    }
+
+    /// Number of times the button was pushed so far.
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Pushes the button once, calling `helper_fn` with every pulse sent.
+    /// Returns the number of low and high pulses.
+    pub fn push<F>(&mut self, mut helper_fn: F) -> (usize, usize)
+    where
+        F: FnMut(&Communication),
+    {
+        self.presses += 1;
+
+        // We are tracking lows and highs for part 1.
+        let mut low = 0;
+        let mut high = 0;
+
+        // Keep track of all the work we have to do, starting with the
+        // button being pushed. We use a VecDeque because the problem
+        // states we need to handle communications in order. So we
+        // want grab from the front and push to the back.
+        let mut queue: VecDeque<Communication> = VecDeque::new();
+        queue.push_back(Communication {
+            source: "button".to_owned(),
+            destination: BROADCASTER.to_owned(),
+            state: Pulse::Low,
+        });
+
+        // Loop through all the work until we are done.
+        while let Some(communication) = queue.pop_front() {
+            // Update our trackers.
+            match communication.state {
+                Pulse::High => high += 1,
+                Pulse::Low => low += 1,
+            }
+
+            helper_fn(&communication);
+
+            // If we don't have a module for the destination, we can move on.
+            let Some(module) = self.modules.get_mut(&communication.destination) else {
+                continue;
+            };
+
+            // Handle the communication and extend our queue with any
+            // new communications.
+            let new_communications = module.handle_communication(&communication);
+            queue.extend(new_communications);
+        }
+
+        // We are done with all the signal handling, return our highs
+        // and lows.
+        (low, high)
+    }
+
+    /// Pushes the button once and returns every pulse in the order it was sent.
+    pub fn push_traced(&mut self) -> Vec<Communication> {
+        let mut trace = vec![];
+        self.push(|communication| trace.push(communication.clone()));
+        trace
+    }
+
+    /// Pushes the button until every probe fired `hits` times or `max_presses` is reached.
+    /// Returns the press counts at which each probe fired.
+    pub fn watch(
+        &mut self,
+        probes: &[Probe],
+        hits: usize,
+        max_presses: usize,
+    ) -> HashMap<Probe, Vec<usize>> {
+        let mut fired: HashMap<Probe, Vec<usize>> =
+            probes.iter().map(|probe| (probe.clone(), vec![])).collect();
+        while self.presses < max_presses && fired.values().any(|presses| presses.len() < hits) {
+            let press = self.presses + 1;
+            self.push(|communication| {
+                for (probe, presses) in fired.iter_mut() {
+                    if probe.module == communication.source
+                        && probe.pulse == communication.state
+                        && presses.last() != Some(&press)
+                        && presses.len() < hits
+                    {
+                        presses.push(press);
+                    }
+                }
+            });
+        }
+        fired
+    }
+
+    /// Period of every probe, taken from its first two hits.
+    pub fn cycles(
+        &mut self,
+        probes: &[Probe],
+        max_presses: usize,
+    ) -> Option<HashMap<Probe, PulseCycle>> {
+        self.watch(probes, 2, max_presses)
+            .into_iter()
+            .map(|(probe, presses)| match presses[..] {
+                [first, second] => Some((
+                    probe,
+                    PulseCycle {
+                        first,
+                        period: second - first,
+                    },
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Presses needed before `module` gets a low pulse, assuming it is fed by a single
+    /// conjunction whose inputs each send a high pulse on a cycle starting at press zero.
+    pub fn presses_until_low(&mut self, module: &str, max_presses: usize) -> Option<usize> {
+        let [feeder] = self.inputs_of(module)[..] else {
+            return None;
+        };
+        if self.kind(feeder) != Some('&') {
+            return None;
+        }
+        let probes = self
+            .inputs_of(feeder)
+            .into_iter()
+            .map(|input| Probe {
+                module: input.to_owned(),
+                pulse: Pulse::High,
+            })
+            .collect::<Vec<_>>();
+        let cycles = self.cycles(&probes, max_presses)?;
+        if cycles.values().any(|cycle| cycle.first != cycle.period) {
+            return None;
+        }
+        cycles.values().map(|cycle| cycle.period).reduce(lcm)
+    }
+}
+
+fn module_name(declared: &str, kind: Option<char>) -> String {
+    match kind {
+        Some(prefix) => declared[prefix.len_utf8()..].to_owned(),
+        None => declared.to_owned(),
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output";
+
+#[test]
+fn test_push() {
+    let mut network = Network::parse(EXAMPLE);
+    let trace = network.push_traced();
+    assert_eq!(trace.len(), 8);
+    assert_eq!(
+        trace[1],
+        Communication {
+            source: BROADCASTER.to_owned(),
+            destination: "a".to_owned(),
+            state: Pulse::Low,
+        }
+    );
+    assert_eq!(network.inputs_of("con"), vec!["a", "b"]);
+    assert_eq!(network.kind("inv"), Some('&'));
+    assert_eq!(network.kind(BROADCASTER), None);
+
+    let (low, high) = (1..1000)
+        .map(|_| network.push(|_| ()))
+        .fold((4, 4), |(l1, h1), (l2, h2)| (l1 + l2, h1 + h2));
+    assert_eq!((low, high), (4250, 2750));
+}
+
+#[test]
+fn test_export() {
+    let network = Network::parse(EXAMPLE);
+    let dot = network.export().to_dot();
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.contains("    \"inv\" [label=\"&inv\", shape=diamond];\n"));
+    assert!(dot.contains("    \"a\" [label=\"%a\", shape=box];\n"));
+    assert!(dot.contains("    \"broadcaster\" [label=\"broadcaster\", shape=circle];\n"));
+    assert!(dot.contains("    \"con\" -> \"output\";\n"));
+}
+
+#[test]
+fn test_watch() {
+    let mut network = Network::parse(EXAMPLE);
+    let probe = Probe {
+        module: "a".to_owned(),
+        pulse: Pulse::High,
+    };
+    let fired = network.watch(std::slice::from_ref(&probe), 3, 100);
+    assert_eq!(fired[&probe], vec![1, 3, 5]);
+
+    let mut network = Network::parse(EXAMPLE);
+    let cycles = network.cycles(std::slice::from_ref(&probe), 100).unwrap();
+    assert_eq!(
+        cycles[&probe],
+        PulseCycle {
+            first: 1,
+            period: 2
+        }
+    );
+}
+
+#[test]
+fn test_presses_until_low() {
+    // binary counters resetting every 3 and 5 presses, feeding a conjunction itself feeding `rx`
+    let input = "broadcaster -> a1, b1
+%a1 -> a2, ca
+%a2 -> ca
+&ca -> a1, ia
+&ia -> hub
+%b1 -> b2, cb
+%b2 -> b3
+%b3 -> cb
+&cb -> b2, b1, ib
+&ib -> hub
+&hub -> rx";
+    let mut network = Network::parse(input);
+    let probes = ["ia", "ib"].map(|module| Probe {
+        module: module.to_owned(),
+        pulse: Pulse::High,
+    });
+    let cycles = network.cycles(&probes, 100).unwrap();
+    assert_eq!(
+        cycles[&probes[0]],
+        PulseCycle {
+            first: 3,
+            period: 3
+        }
+    );
+    assert_eq!(
+        cycles[&probes[1]],
+        PulseCycle {
+            first: 5,
+            period: 5
+        }
+    );
+    let mut network = Network::parse(input);
+    assert_eq!(network.presses_until_low("rx", 100), Some(15));
+}
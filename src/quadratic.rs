@@ -0,0 +1,64 @@
+use num::BigUint;
+
+// t·(total - t) > threshold, without computing the product
+fn beats(t: u128, total: u128, threshold: u128) -> bool {
+    t > 0 && t < total && total - t > threshold / t
+}
+
+/// Number of integers `t` in `[0, total]` with `t·(total - t) > threshold`.
+/// The roots of `t² - total·t + threshold` come from an integer square root on big integers,
+/// so every `u128` input is handled exactly.
+pub fn count_products_above(total: u128, threshold: u128) -> u128 {
+    let (total_big, threshold_big) = (BigUint::from(total), BigUint::from(threshold));
+    let squared = &total_big * &total_big;
+    let four_threshold = threshold_big * 4u32;
+    if squared <= four_threshold {
+        return 0;
+    }
+    let root = u128::try_from((squared - four_threshold).sqrt()).unwrap_or(total);
+
+    // the integer root is only off by one, fix the lower bound on the exact predicate
+    let mut low = (total - root.min(total)) / 2;
+    while low <= total / 2 && !beats(low, total, threshold) {
+        low += 1;
+    }
+    while low > 0 && beats(low - 1, total, threshold) {
+        low -= 1;
+    }
+    if low > total / 2 {
+        return 0;
+    }
+    // products are symmetric around total / 2
+    total - 2 * low + 1
+}
+
+#[test]
+fn test_count_products_above() {
+    assert_eq!(count_products_above(7, 9), 4);
+    assert_eq!(count_products_above(15, 40), 8);
+    assert_eq!(count_products_above(30, 200), 9);
+    assert_eq!(count_products_above(71530, 940200), 71503);
+    // 2·2 = 4 only equals the record
+    assert_eq!(count_products_above(4, 4), 0);
+    assert_eq!(count_products_above(4, 3), 1);
+    assert_eq!(count_products_above(0, 0), 0);
+}
+
+#[test]
+fn test_count_products_above_brute_force() {
+    for total in 0..40u128 {
+        for threshold in 0..400 {
+            let expected = (0..=total).filter(|t| t * (total - t) > threshold).count() as u128;
+            assert_eq!(count_products_above(total, threshold), expected);
+        }
+    }
+}
+
+#[test]
+fn test_count_products_above_huge() {
+    // only the first and last two hold times fall short
+    let total = u128::MAX - 1;
+    let half = total / 2;
+    assert_eq!(count_products_above(total, u128::MAX), total - 3);
+    assert_eq!(count_products_above(2 * half, half), total - 1);
+}
@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: HashMap<u8, usize>,
+    /// Longest proper suffix of this node also in the trie.
+    fail: usize,
+    /// Pattern ending exactly here.
+    pattern: Option<usize>,
+    /// Closest node along the failure links where a pattern ends.
+    dictionary: Option<usize>,
+}
+
+/// A pattern found in a text, covering `text[start..end]`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'s, V> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'s V,
+}
+
+impl<V> Clone for Match<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Match<'_, V> {}
+
+/// Aho–Corasick automaton: finds every occurrence of many patterns in a single pass over
+/// the text, overlapping ones included.
+#[derive(Debug, Clone)]
+pub struct Scanner<V> {
+    nodes: Vec<Node>,
+    patterns: Vec<(usize, V)>,
+}
+
+impl<'p, V> FromIterator<(&'p str, V)> for Scanner<V> {
+    fn from_iter<I: IntoIterator<Item = (&'p str, V)>>(patterns: I) -> Self {
+        let mut scanner = Scanner {
+            nodes: vec![Node::default()],
+            patterns: vec![],
+        };
+        for (pattern, value) in patterns {
+            scanner.insert(pattern, value);
+        }
+        scanner.link();
+        scanner
+    }
+}
+
+impl<V> Scanner<V> {
+    // a pattern given twice keeps its first value
+    fn insert(&mut self, pattern: &str, value: V) {
+        let mut node = 0;
+        for &byte in pattern.as_bytes() {
+            node = match self.nodes[node].children.get(&byte) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(byte, child);
+                    child
+                }
+            };
+        }
+        if node != 0 && self.nodes[node].pattern.is_none() {
+            self.nodes[node].pattern = Some(self.patterns.len());
+            self.patterns.push((pattern.len(), value));
+        }
+    }
+
+    // failure and dictionary links, breadth first so that shorter suffixes come first
+    fn link(&mut self) {
+        let mut queue = self.nodes[0]
+            .children
+            .values()
+            .copied()
+            .collect::<VecDeque<_>>();
+        while let Some(node) = queue.pop_front() {
+            let children = self.nodes[node]
+                .children
+                .iter()
+                .map(|(&byte, &child)| (byte, child))
+                .collect::<Vec<_>>();
+            for (byte, child) in children {
+                let mut fail = self.nodes[node].fail;
+                while fail != 0 && !self.nodes[fail].children.contains_key(&byte) {
+                    fail = self.nodes[fail].fail;
+                }
+                let fail = self.nodes[fail].children.get(&byte).copied().unwrap_or(0);
+                self.nodes[child].fail = fail;
+                self.nodes[child].dictionary = if self.nodes[fail].pattern.is_some() {
+                    Some(fail)
+                } else {
+                    self.nodes[fail].dictionary
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn next(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&child) = self.nodes[node].children.get(&byte) {
+                return child;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    fn scan<'s>(&'s self, text: &str, mut on_match: impl FnMut(Match<'s, V>)) {
+        let mut node = 0;
+        for (index, &byte) in text.as_bytes().iter().enumerate() {
+            node = self.next(node, byte);
+            let mut output = Some(node).filter(|&node| self.nodes[node].pattern.is_some());
+            output = output.or(self.nodes[node].dictionary);
+            while let Some(found) = output {
+                let (length, value) = &self.patterns[self.nodes[found].pattern.unwrap()];
+                on_match(Match {
+                    start: index + 1 - length,
+                    end: index + 1,
+                    value,
+                });
+                output = self.nodes[found].dictionary;
+            }
+        }
+    }
+
+    /// Every match, ordered by where it ends then from the longest to the shortest.
+    pub fn find_all(&self, text: &str) -> Vec<Match<'_, V>> {
+        let mut matches = vec![];
+        self.scan(text, |found| matches.push(found));
+        matches
+    }
+
+    /// The matches starting first and last, found in the same pass.
+    pub fn first_and_last(&self, text: &str) -> Option<(Match<'_, V>, Match<'_, V>)> {
+        let mut bounds: Option<(Match<'_, V>, Match<'_, V>)> = None;
+        self.scan(text, |found| {
+            bounds = Some(match bounds {
+                None => (found, found),
+                Some((first, last)) => (
+                    if found.start < first.start {
+                        found
+                    } else {
+                        first
+                    },
+                    if found.start > last.start {
+                        found
+                    } else {
+                        last
+                    },
+                ),
+            });
+        });
+        bounds
+    }
+}
+
+#[test]
+fn test_overlapping_matches() {
+    let scanner = Scanner::from_iter([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+    let matches = scanner
+        .find_all("ushers")
+        .into_iter()
+        .map(|found| (found.start, found.end, *found.value))
+        .collect::<Vec<_>>();
+    assert_eq!(matches, vec![(1, 4, 2), (2, 4, 1), (2, 6, 4)]);
+    assert!(scanner.find_all("xyz").is_empty());
+}
+
+#[test]
+fn test_first_and_last() {
+    let scanner = Scanner::from_iter([("one", 1), ("two", 2), ("eight", 8), ("8", 8)]);
+    let (first, last) = scanner.first_and_last("xeightwone").unwrap();
+    assert_eq!((first.start, *first.value), (1, 8));
+    assert_eq!((last.start, *last.value), (7, 1));
+    assert_eq!(scanner.first_and_last("nothing"), None);
+}
+
+#[test]
+fn test_nested_patterns() {
+    let scanner = Scanner::from_iter([("a", 'a'), ("aa", 'b'), ("aaa", 'c')]);
+    let values = scanner
+        .find_all("aaa")
+        .iter()
+        .map(|found| *found.value)
+        .collect::<String>();
+    assert_eq!(values, "abacba");
+}
@@ -0,0 +1,92 @@
+/// Polynomial going through the samples `y(0), y(1), ..., y(n - 1)`, kept in Newton form:
+/// `y(x) = Σ coefficients[k] · C(x, k)`, which stays exact on integers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<i128>,
+    samples: usize,
+}
+
+impl Polynomial {
+    /// Lowest-degree polynomial through `values`, as long as its degree is at most
+    /// `max_degree`. A sequence of `n` values always fits a polynomial of degree `n - 1`.
+    pub fn fit(values: &[i64], max_degree: usize) -> Option<Polynomial> {
+        let mut row = values.iter().map(|&v| v as i128).collect::<Vec<_>>();
+        let mut coefficients = vec![];
+        while row.iter().any(|&value| value != 0) {
+            if coefficients.len() > max_degree {
+                return None;
+            }
+            coefficients.push(row[0]);
+            row = row.windows(2).map(|w| w[1] - w[0]).collect();
+        }
+        (!values.is_empty()).then_some(Polynomial {
+            coefficients,
+            samples: values.len(),
+        })
+    }
+
+    /// Degree of the polynomial, the null one having none.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Number of samples beyond the ones needed to determine the polynomial: without any,
+    /// the fit is only a guess.
+    pub fn confirmations(&self) -> usize {
+        self.samples - self.coefficients.len().min(self.samples)
+    }
+
+    pub fn value_at(&self, x: i64) -> i128 {
+        let x = x as i128;
+        let mut binomial = 1;
+        let mut value = 0;
+        for (k, coefficient) in self.coefficients.iter().enumerate() {
+            value += coefficient * binomial;
+            // C(x, k + 1) = C(x, k) · (x - k) / (k + 1), the division being exact
+            binomial = binomial * (x - k as i128) / (k as i128 + 1);
+        }
+        value
+    }
+
+    /// Value `steps` positions after the last sample.
+    pub fn forwards(&self, steps: usize) -> i128 {
+        self.value_at((self.samples - 1 + steps) as i64)
+    }
+
+    /// Value `steps` positions before the first sample.
+    pub fn backwards(&self, steps: usize) -> i128 {
+        self.value_at(-(steps as i64))
+    }
+}
+
+#[test]
+fn test_fit() {
+    let polynomial = Polynomial::fit(&[1, 3, 6, 10, 15, 21], 5).unwrap();
+    assert_eq!(polynomial.degree(), Some(2));
+    assert_eq!(polynomial.confirmations(), 3);
+    assert_eq!(polynomial.forwards(1), 28);
+    assert_eq!(polynomial.forwards(3), 45);
+    assert_eq!(polynomial.backwards(1), 0);
+    assert_eq!(polynomial.backwards(2), 0);
+    assert_eq!(polynomial.backwards(3), 1);
+
+    let constant = Polynomial::fit(&[0, 0, 0], 0).unwrap();
+    assert_eq!(constant.degree(), None);
+    assert_eq!(constant.forwards(10), 0);
+    assert_eq!(Polynomial::fit(&[], 2), None);
+}
+
+#[test]
+fn test_no_low_degree_fit() {
+    let powers = [1, 2, 4, 8, 16, 32, 64];
+    assert_eq!(Polynomial::fit(&powers, 3), None);
+    let polynomial = Polynomial::fit(&powers, 6).unwrap();
+    assert_eq!(polynomial.confirmations(), 0);
+}
+
+#[test]
+fn test_quadratic_growth() {
+    // reachable plots growing quadratically every 131 steps, as in day 21
+    let polynomial = Polynomial::fit(&[3_917, 34_920, 96_829], 2).unwrap();
+    assert_eq!(polynomial.value_at(202_300), 632_421_652_138_917);
+}
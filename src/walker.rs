@@ -0,0 +1,251 @@
+use std::{collections::HashMap, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::{
+    cycle::{chinese_remainder, Cycle},
+    graph::export::GraphExport,
+};
+
+/// Nodes with a left and a right neighbour, walked by following a repeating list of turns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// `false` for left, `true` for right.
+    instructions: Vec<bool>,
+    names: Vec<String>,
+    next: Vec<[usize; 2]>,
+}
+
+impl FromStr for Network {
+    type Err = String;
+    /// Parses the `LLR` instruction line followed by `AAA = (BBB, CCC)` nodes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let instructions = lines
+            .next()
+            .ok_or("missing instructions")?
+            .chars()
+            .map(|instruction| match instruction {
+                'L' => Ok(false),
+                'R' => Ok(true),
+                _ => Err(format!("unknown instruction {instruction}")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let nodes = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (source, destinations) = line
+                    .split_once(" = ")
+                    .and_then(|(source, destinations)| {
+                        let destinations = destinations
+                            .strip_prefix('(')?
+                            .strip_suffix(')')?
+                            .split_once(", ")?;
+                        Some((source, destinations))
+                    })
+                    .ok_or_else(|| format!("invalid node {line}"))?;
+                Ok((source, destinations))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (*name, index))
+            .collect::<HashMap<_, _>>();
+        let index_of = |name: &str| {
+            indices
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("unknown node {name}"))
+        };
+        let next = nodes
+            .iter()
+            .map(|(_, (left, right))| Ok([index_of(left)?, index_of(right)?]))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Network {
+            instructions,
+            names: nodes.iter().map(|(name, _)| name.to_string()).collect(),
+            next,
+        })
+    }
+}
+
+/// The steps at which a walker stands on a target node. Hits before `prefix` happen once,
+/// the others repeat every `length` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orbit {
+    pub prefix: u64,
+    pub length: u64,
+    pub hits: Vec<u64>,
+}
+
+impl Orbit {
+    pub fn hits_at(&self, step: u64) -> bool {
+        let step = if step < self.prefix {
+            step
+        } else {
+            self.prefix + (step - self.prefix) % self.length
+        };
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    /// Congruences `step ≡ hit (mod length)` followed by repeating hits.
+    fn congruences(&self) -> Vec<(u64, u64)> {
+        self.hits
+            .iter()
+            .filter(|&&hit| hit >= self.prefix)
+            .map(|hit| (hit % self.length, self.length))
+            .collect()
+    }
+}
+
+impl Network {
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Directed graph of the nodes, each leading to its left and right neighbours.
+    pub fn export(&self) -> GraphExport {
+        GraphExport::directed(
+            self.names
+                .iter()
+                .zip(&self.next)
+                .map(|(name, next)| (name, next.map(|index| &self.names[index]))),
+        )
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Moves to the next node, the state being the node and the position in the instructions.
+    fn step(&self, (node, instruction): &(usize, usize)) -> (usize, usize) {
+        let turn = self.instructions[*instruction] as usize;
+        (
+            self.next[*node][turn],
+            (instruction + 1) % self.instructions.len(),
+        )
+    }
+
+    pub fn orbit(&self, start: &str, is_target: impl Fn(&str) -> bool) -> Option<Orbit> {
+        let start = self.index_of(start)?;
+        let (cycle, states) = Cycle::find_hashed((start, 0), |state| self.step(state));
+        Some(Orbit {
+            prefix: cycle.start as u64,
+            length: cycle.length as u64,
+            hits: states
+                .iter()
+                .positions(|(node, _)| is_target(&self.names[*node]))
+                .map(|step| step as u64)
+                .collect(),
+        })
+    }
+
+    /// Number of steps from `start` to the first target, `None` if none is ever reached.
+    pub fn steps(&self, start: &str, is_target: impl Fn(&str) -> bool) -> Option<u64> {
+        self.orbit(start, is_target)?.hits.first().copied()
+    }
+
+    /// Number of steps until walkers leaving every start at once all stand on a target.
+    pub fn steps_together(
+        &self,
+        is_start: impl Fn(&str) -> bool,
+        is_target: impl Fn(&str) -> bool,
+    ) -> Option<u64> {
+        let orbits = self
+            .nodes()
+            .filter(|name| is_start(name))
+            .map(|name| self.orbit(name, &is_target))
+            .collect::<Option<Vec<_>>>()?;
+        first_common_hit(&orbits)
+    }
+}
+
+/// The first step at which every orbit hits, solving one congruence system per
+/// combination of repeating hits once all prefixes are over.
+pub fn first_common_hit(orbits: &[Orbit]) -> Option<u64> {
+    let prefix = orbits.iter().map(|orbit| orbit.prefix).max()?;
+    // before every walker has entered its cycle
+    let early = (0..prefix).find(|&step| orbits.iter().all(|orbit| orbit.hits_at(step)));
+    if early.is_some() {
+        return early;
+    }
+
+    orbits
+        .iter()
+        .map(Orbit::congruences)
+        .multi_cartesian_product()
+        .filter_map(|congruences| chinese_remainder(&congruences))
+        .map(|(residue, modulus)| {
+            if residue >= prefix {
+                residue
+            } else {
+                residue + (prefix - residue).div_ceil(modulus) * modulus
+            }
+        })
+        .min()
+}
+
+#[cfg(test)]
+fn example() -> Network {
+    Network::from_str(
+        "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_orbit() {
+    let network = example();
+    let orbit = network.orbit("22A", |name| name.ends_with('Z')).unwrap();
+    assert_eq!(
+        orbit,
+        Orbit {
+            prefix: 1,
+            length: 6,
+            hits: vec![3, 6]
+        }
+    );
+    assert!(orbit.hits_at(9) && orbit.hits_at(12) && !orbit.hits_at(10));
+    assert_eq!(network.steps("11A", |name| name == "11Z"), Some(2));
+    assert_eq!(network.steps("XXX", |name| name == "11Z"), None);
+}
+
+#[test]
+fn test_steps_together() {
+    let network = example();
+    assert_eq!(
+        network.steps_together(|name| name.ends_with('A'), |name| name.ends_with('Z')),
+        Some(6)
+    );
+}
+
+#[test]
+fn test_first_common_hit_with_offsets() {
+    // 4, 9, 14, ... and 2, 5, 8, 11, 14, ... after prefixes that do not align
+    let a = Orbit {
+        prefix: 4,
+        length: 5,
+        hits: vec![1, 4],
+    };
+    let b = Orbit {
+        prefix: 2,
+        length: 3,
+        hits: vec![1, 2],
+    };
+    assert_eq!(first_common_hit(&[a.clone(), b.clone()]), Some(1));
+    let a = Orbit { hits: vec![4], ..a };
+    assert_eq!(first_common_hit(&[a, b]), Some(14));
+}
@@ -0,0 +1,502 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::{
+    interval::Interval,
+    parse::{
+        delimited, integer, key_value, line_end, lines, many, map, one_of, pair, parse_all,
+        separated, tag, terminated, word, Parser,
+    },
+};
+
+pub const START: &str = "in";
+pub const ACCEPTED: &str = "A";
+pub const REJECTED: &str = "R";
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Category {
+    Extremely = 0,
+    Musical = 1,
+    Aerodynamic = 2,
+    Shiny = 3,
+}
+
+impl From<char> for Category {
+    fn from(c: char) -> Self {
+        match c {
+            'x' => Self::Extremely,
+            'm' => Self::Musical,
+            'a' => Self::Aerodynamic,
+            's' => Self::Shiny,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Ratings of a part, indexed by `Category`.
+pub type Part = [usize; 4];
+
+fn category<'a>() -> impl Parser<'a, Category> {
+    map(one_of("xmas"), Category::from)
+}
+
+fn part<'a>() -> impl Parser<'a, Part> {
+    let rating = key_value(category(), "=", integer());
+    map(
+        delimited(tag("{"), separated(rating, tag(",")), tag("}")),
+        |ratings| {
+            let mut part = [0; 4];
+            for (category, value) in ratings {
+                part[category as usize] = value;
+            }
+            part
+        },
+    )
+}
+
+/// Parses `{x=787,m=2655,a=1222,s=2876}`.
+pub fn parse_part(s: &str) -> Result<Part, String> {
+    Ok(parse_all(part(), s)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    GreaterThan,
+    LowerThan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub testing: Category,
+    pub comparing_to: usize,
+    pub operand: Operand,
+    pub destination: String,
+}
+
+impl Rule {
+    fn matches(&self, value: usize) -> bool {
+        match self.operand {
+            Operand::GreaterThan => value > self.comparing_to,
+            Operand::LowerThan => value < self.comparing_to,
+        }
+    }
+
+    pub fn verify(&self, part: &Part) -> Option<&str> {
+        self.matches(part[self.testing as usize])
+            .then_some(self.destination.as_str())
+    }
+
+    /// Splits `rectangle` into the parts matching and not matching the rule.
+    pub fn split(
+        &self,
+        rectangle: &HyperRectangle,
+    ) -> (Option<HyperRectangle>, Option<HyperRectangle>) {
+        let (lower, upper) = match self.operand {
+            Operand::GreaterThan => rectangle.split(self.testing, self.comparing_to as u64 + 1),
+            Operand::LowerThan => rectangle.split(self.testing, self.comparing_to as u64),
+        };
+        match self.operand {
+            Operand::GreaterThan => (upper, lower),
+            Operand::LowerThan => (lower, upper),
+        }
+    }
+}
+
+fn rule<'a>() -> impl Parser<'a, Rule> {
+    let operand = map(one_of("<>"), |operand| match operand {
+        '>' => Operand::GreaterThan,
+        _ => Operand::LowerThan,
+    });
+    map(
+        pair(pair(category(), operand), key_value(integer(), ":", word)),
+        |((testing, operand), (comparing_to, destination))| Rule {
+            testing,
+            comparing_to,
+            operand,
+            destination: destination.to_string(),
+        },
+    )
+}
+
+impl FromStr for Rule {
+    type Err = String;
+    /// Parses `a<2006:qkq`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(rule(), s)?)
+    }
+}
+
+/// The rules of a single workflow, tried in order before falling back to `default`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub rules: Vec<Rule>,
+    pub default: String,
+}
+
+impl Rules {
+    pub fn apply(&self, part: &Part) -> &str {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.verify(part))
+            .unwrap_or(&self.default)
+    }
+
+    fn destinations(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .map(|rule| rule.destination.as_str())
+            .chain([self.default.as_str()])
+    }
+}
+
+fn rules<'a>() -> impl Parser<'a, Rules> {
+    map(
+        pair(many(terminated(rule(), tag(","))), word),
+        |(rules, default)| Rules {
+            rules,
+            default: default.to_string(),
+        },
+    )
+}
+
+impl FromStr for Rules {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(rules(), s)?)
+    }
+}
+
+/// Ranges of ratings, one half-open interval per `Category`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HyperRectangle(pub [Interval; 4]);
+
+impl HyperRectangle {
+    pub fn uniform(interval: Interval) -> Self {
+        Self([interval; 4])
+    }
+
+    pub fn volume(&self) -> u64 {
+        self.0.iter().map(Interval::len).product()
+    }
+
+    /// Splits along `category` into the values lower than `at` and the others.
+    pub fn split(&self, category: Category, at: u64) -> (Option<Self>, Option<Self>) {
+        let (lower, upper) = self.0[category as usize].split_at(at);
+        let with = |interval: Interval| {
+            let mut rectangle = *self;
+            rectangle.0[category as usize] = interval;
+            rectangle
+        };
+        (lower.map(with), upper.map(with))
+    }
+}
+
+/// Workflows flattened into a binary decision tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Accept,
+    Reject,
+    Test {
+        rule: Rule,
+        then: Box<Decision>,
+        otherwise: Box<Decision>,
+    },
+}
+
+impl Decision {
+    pub fn evaluate(&self, part: &Part) -> bool {
+        match self {
+            Decision::Accept => true,
+            Decision::Reject => false,
+            Decision::Test {
+                rule,
+                then,
+                otherwise,
+            } => {
+                if rule.verify(part).is_some() {
+                    then.evaluate(part)
+                } else {
+                    otherwise.evaluate(part)
+                }
+            }
+        }
+    }
+
+    /// Number of parts inside `rectangle` reaching `Accept`.
+    pub fn count_accepted(&self, rectangle: &HyperRectangle) -> u64 {
+        match self {
+            Decision::Accept => rectangle.volume(),
+            Decision::Reject => 0,
+            Decision::Test {
+                rule,
+                then,
+                otherwise,
+            } => {
+                let (matching, rest) = rule.split(rectangle);
+                matching.map_or(0, |r| then.count_accepted(&r))
+                    + rest.map_or(0, |r| otherwise.count_accepted(&r))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflows {
+    pub workflows: HashMap<String, Rules>,
+}
+
+fn workflows<'a>() -> impl Parser<'a, Workflows> {
+    let workflow = pair(
+        map(word, str::to_string),
+        delimited(tag("{"), rules(), tag("}")),
+    );
+    map(lines(workflow), |workflows| Workflows {
+        workflows: workflows.into_iter().collect(),
+    })
+}
+
+impl FromStr for Workflows {
+    type Err = String;
+    /// Parses one `px{a<2006:qkq,m>2090:A,rfg}` workflow per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(workflows(), s)?)
+    }
+}
+
+/// Parses the workflows, a blank line, then one part per line.
+pub fn parse_system(s: &str) -> Result<(Workflows, Vec<Part>), String> {
+    Ok(parse_all(
+        pair(terminated(workflows(), line_end), lines(part())),
+        s,
+    )?)
+}
+
+impl Workflows {
+    /// Follows the workflows one by one, starting at `in`.
+    pub fn accepts(&self, part: &Part) -> Result<bool, String> {
+        let mut current = START;
+        let mut visited = HashSet::new();
+        while visited.insert(current) {
+            match current {
+                ACCEPTED => return Ok(true),
+                REJECTED => return Ok(false),
+                _ => current = self.get(current)?.apply(part),
+            }
+        }
+        Err(format!("workflow {current} loops"))
+    }
+
+    fn get(&self, id: &str) -> Result<&Rules, String> {
+        self.workflows
+            .get(id)
+            .ok_or_else(|| format!("unknown workflow {id}"))
+    }
+
+    pub fn compile(&self) -> Result<Decision, String> {
+        self.compile_from(START, 0, &mut vec![])
+    }
+
+    fn compile_from(
+        &self,
+        id: &str,
+        rule_index: usize,
+        stack: &mut Vec<String>,
+    ) -> Result<Decision, String> {
+        match id {
+            ACCEPTED => return Ok(Decision::Accept),
+            REJECTED => return Ok(Decision::Reject),
+            _ => {}
+        }
+        let rules = self.get(id)?;
+        let Some(rule) = rules.rules.get(rule_index) else {
+            return self.compile_workflow(&rules.default, stack);
+        };
+        Ok(Decision::Test {
+            rule: rule.clone(),
+            then: Box::new(self.compile_workflow(&rule.destination, stack)?),
+            otherwise: Box::new(self.compile_from(id, rule_index + 1, stack)?),
+        })
+    }
+
+    fn compile_workflow(&self, id: &str, stack: &mut Vec<String>) -> Result<Decision, String> {
+        if stack.iter().any(|visited| visited == id) {
+            return Err(format!("workflow {id} loops"));
+        }
+        stack.push(id.to_owned());
+        let decision = self.compile_from(id, 0, stack);
+        stack.pop();
+        decision
+    }
+
+    /// Number of parts with every rating inside `range` that end up accepted.
+    pub fn accepted_combinations(&self, range: Interval) -> Result<u64, String> {
+        Ok(self
+            .compile()?
+            .count_accepted(&HyperRectangle::uniform(range)))
+    }
+
+    // volume entering and accepted through each workflow, for parts rated inside `range`
+    fn flows(&self, range: Interval) -> HashMap<&str, (u64, u64)> {
+        let mut flows = HashMap::new();
+        self.propagate(
+            START,
+            HyperRectangle::uniform(range),
+            &mut flows,
+            &mut vec![],
+        );
+        flows
+    }
+
+    fn propagate<'a>(
+        &'a self,
+        id: &'a str,
+        rectangle: HyperRectangle,
+        flows: &mut HashMap<&'a str, (u64, u64)>,
+        stack: &mut Vec<&'a str>,
+    ) -> u64 {
+        match id {
+            ACCEPTED => return rectangle.volume(),
+            REJECTED => return 0,
+            _ => {}
+        }
+        let Some(rules) = self.workflows.get(id) else {
+            return 0;
+        };
+        if stack.contains(&id) {
+            return 0;
+        }
+        stack.push(id);
+        let mut accepted = 0;
+        let mut rest = Some(rectangle);
+        for rule in &rules.rules {
+            let Some(current) = rest else {
+                break;
+            };
+            let (matching, remaining) = rule.split(&current);
+            if let Some(matching) = matching {
+                accepted += self.propagate(&rule.destination, matching, flows, stack);
+            }
+            rest = remaining;
+        }
+        if let Some(rest) = rest {
+            accepted += self.propagate(&rules.default, rest, flows, stack);
+        }
+        stack.pop();
+
+        let flow = flows.entry(id).or_default();
+        flow.0 += rectangle.volume();
+        flow.1 += accepted;
+        accepted
+    }
+
+    /// Workflows that no part rated inside `range` ever enters, including ones never
+    /// referenced and ones only reached through impossible conditions.
+    pub fn unreachable(&self, range: Interval) -> Vec<&str> {
+        let flows = self.flows(range);
+        let mut unreachable = self
+            .workflows
+            .keys()
+            .map(|id| id.as_str())
+            .filter(|id| flows.get(id).map_or(0, |flow| flow.0) == 0)
+            .collect::<Vec<_>>();
+        unreachable.sort();
+        unreachable
+    }
+
+    /// Workflows entered by some parts but rejecting every one of them.
+    pub fn dead(&self, range: Interval) -> Vec<&str> {
+        let flows = self.flows(range);
+        let mut dead = flows
+            .iter()
+            .filter(|(_, (entered, accepted))| *entered > 0 && *accepted == 0)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        dead.sort();
+        dead
+    }
+
+    /// Workflows referencing a workflow that does not exist.
+    pub fn dangling(&self) -> Vec<(&str, &str)> {
+        let mut dangling = self
+            .workflows
+            .iter()
+            .flat_map(|(id, rules)| rules.destinations().map(move |d| (id.as_str(), d)))
+            .filter(|(_, d)| ![ACCEPTED, REJECTED].contains(d) && !self.workflows.contains_key(*d))
+            .collect::<Vec<_>>();
+        dangling.sort();
+        dangling
+    }
+}
+
+#[test]
+fn test_rule_verify() {
+    let rule = Rule::from_str("a<2006:qkq").unwrap();
+    let mut part = [0; 4];
+    assert_eq!(rule.verify(&part), Some("qkq"));
+    let rule = Rule::from_str("m>2090:A").unwrap();
+    part[Category::Musical as usize] = 3000;
+    assert_eq!(rule.verify(&part), Some("A"));
+    part[Category::Musical as usize] = 2000;
+    assert_eq!(rule.verify(&part), None);
+}
+
+#[test]
+fn test_rules_apply() {
+    let rules = Rules::from_str("a<2006:qkq,m>2090:A,rfg").unwrap();
+    let mut part = [0; 4];
+    assert_eq!(rules.apply(&part), "qkq");
+    part[Category::Aerodynamic as usize] = 3000;
+    part[Category::Musical as usize] = 3000;
+    assert_eq!(rules.apply(&part), "A");
+    part[Category::Musical as usize] = 2000;
+    assert_eq!(rules.apply(&part), "rfg");
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse_part("{x=787,q=2655}"),
+        Err("line 1, column 8: expected one of \"xmas\"".to_string())
+    );
+    let error = Workflows::from_str("in{x>10:A,R}\npx{a<2006qkq,R}").unwrap_err();
+    assert_eq!(error, "line 2, column 5: expected \"}\"");
+    let (workflows, parts) = parse_system("in{x>10:A,R}\n\n{x=1,m=2,a=3,s=4}\n").unwrap();
+    assert_eq!(workflows.workflows.len(), 1);
+    assert_eq!(parts, vec![[1, 2, 3, 4]]);
+}
+
+#[test]
+fn test_decision_tree() {
+    let workflows =
+        Workflows::from_str("in{x>10:A,m<5:low,R}\nlow{s>2:A,R}\nnever{a<3:A,R}\nnope{x<5:R,R}")
+            .unwrap();
+    let decision = workflows.compile().unwrap();
+    assert!(decision.evaluate(&parse_part("{x=11,m=0,a=0,s=0}").unwrap()));
+    assert!(decision.evaluate(&parse_part("{x=1,m=1,a=0,s=3}").unwrap()));
+    assert!(!decision.evaluate(&parse_part("{x=1,m=1,a=0,s=2}").unwrap()));
+    assert_eq!(
+        workflows.accepts(&parse_part("{x=1,m=1,a=0,s=3}").unwrap()),
+        Ok(true)
+    );
+
+    // ratings 1 to 20: 10 * 20^3 with x > 10, plus 10 * 4 * 20 * 18 for the low path
+    let range = Interval::new(1, 21);
+    assert_eq!(workflows.accepted_combinations(range), Ok(80000 + 14400));
+    assert_eq!(workflows.unreachable(range), vec!["never", "nope"]);
+    assert_eq!(workflows.dead(range), Vec::<&str>::new());
+}
+
+#[test]
+fn test_dead_and_looping_workflows() {
+    let workflows = Workflows::from_str("in{x>10:A,a<5:lost,R}\nlost{s>2:R,R}").unwrap();
+    assert_eq!(workflows.dead(Interval::new(1, 21)), vec!["lost"]);
+    assert_eq!(workflows.dangling(), vec![]);
+
+    let workflows = Workflows::from_str("in{x>10:A,loop}\nloop{in}").unwrap();
+    assert!(workflows.compile().is_err());
+    assert_eq!(workflows.dangling(), vec![]);
+    let workflows = Workflows::from_str("in{x>10:A,gone}").unwrap();
+    assert_eq!(workflows.dangling(), vec![("in", "gone")]);
+}